@@ -0,0 +1,74 @@
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import { fileURLToPath } from "url";
+
+const __dirname = path.dirname(fileURLToPath(import.meta.url));
+
+// Configuration
+const MANIFEST_PATH = path.join(
+  __dirname,
+  "..",
+  "src-tauri",
+  "resources",
+  "models.json",
+);
+
+interface ManifestModel {
+  id: string;
+  url?: string;
+  sha256?: string;
+}
+
+// Downloads a file and returns its lowercase hex SHA-256 digest
+async function sha256OfUrl(url: string): Promise<string> {
+  const response = await fetch(url);
+  if (!response.ok || !response.body) {
+    throw new Error(`${url} returned ${response.status}`);
+  }
+  const hash = crypto.createHash("sha256");
+  for await (const chunk of response.body) {
+    hash.update(chunk);
+  }
+  return hash.digest("hex");
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+// Writes the digest next to the model's url, keeping the file's hand-made layout
+function setChecksum(manifest: string, url: string, digest: string): string {
+  const urlLine = new RegExp(
+    `^(\\s*)"url": "${escapeRegExp(url)}",\\n(\\s*"sha256": "[^"]*",\\n)?`,
+    "m",
+  );
+  return manifest.replace(
+    urlLine,
+    (_match, indent: string) =>
+      `${indent}"url": "${url}",\n${indent}"sha256": "${digest}",\n`,
+  );
+}
+
+async function main(): Promise<void> {
+  const force = process.argv.includes("--force");
+  let manifest = fs.readFileSync(MANIFEST_PATH, "utf8");
+  const models: ManifestModel[] = JSON.parse(manifest).models;
+
+  for (const model of models) {
+    if (!model.url || (model.sha256 && !force)) {
+      continue;
+    }
+    console.log(`Hashing ${model.id} from ${model.url}`);
+    const digest = await sha256OfUrl(model.url);
+    console.log(`  ${digest}`);
+    manifest = setChecksum(manifest, model.url, digest);
+  }
+
+  fs.writeFileSync(MANIFEST_PATH, manifest);
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
@@ -1,22 +1,32 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::clipboard::StreamingPaste;
+use crate::helpers::active_app;
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
+use crate::settings::{
+    get_settings, AppSettings, ClipboardHandling, PasteMethod, PostProcessProvider,
+    ShortcutBinding, APPLE_INTELLIGENCE_PROVIDER_ID,
+};
 use crate::shortcut;
+use crate::snippets;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils::{self, show_recording_overlay, show_transcribing_overlay};
+use crate::voice_commands;
 use crate::ManagedToggleState;
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
 use log::{debug, error};
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use tauri::AppHandle;
+use tauri::Emitter;
 use tauri::Manager;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 // Shortcut Action Trait
 pub trait ShortcutAction: Send + Sync {
@@ -24,35 +34,119 @@ pub trait ShortcutAction: Send + Sync {
     fn stop(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
 }
 
+/// Emitted once a transcribe binding has finished, whether or not any text was produced.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TranscriptionCompleteEvent {
+    pub binding_id: String,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+fn emit_transcription_complete(
+    app: &AppHandle,
+    binding_id: &str,
+    text: &str,
+    error: Option<String>,
+) {
+    let _ = app.emit(
+        "transcription-complete",
+        TranscriptionCompleteEvent {
+            binding_id: binding_id.to_string(),
+            text: text.to_string(),
+            error,
+        },
+    );
+}
+
 // Transcribe Action
+#[derive(Clone, Default)]
 struct TranscribeAction {
     post_process: bool,
+    /// Run a matching voice command instead of pasting
+    command_mode: bool,
+    /// Per-binding overrides; `None` falls back to the global setting
+    prompt_id: Option<String>,
+    model_id: Option<String>,
+    language: Option<String>,
 }
 
-async fn post_process_transcription(settings: &AppSettings, transcription: &str) -> Option<String> {
-    let provider = match settings.active_post_process_provider().cloned() {
-        Some(provider) => provider,
-        None => {
-            debug!("Post-processing enabled but no provider is selected");
-            return None;
+impl TranscribeAction {
+    fn for_binding(binding: &ShortcutBinding) -> Self {
+        TranscribeAction {
+            post_process: binding.id == "transcribe_with_post_process"
+                || binding.prompt_id.is_some(),
+            command_mode: binding.id == "voice_command",
+            prompt_id: binding.prompt_id.clone(),
+            model_id: binding.model_id.clone(),
+            language: binding.language.clone(),
         }
-    };
+    }
 
-    let model = settings
-        .post_process_models
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
+    /// Apply the binding's overrides on top of the app profile ones, since the
+    /// user picked this binding explicitly.
+    fn apply_overrides(&self, settings: &mut AppSettings) {
+        if let Some(prompt_id) = &self.prompt_id {
+            settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+            settings.post_process_language_prompts.clear();
+        }
+        if let Some(language) = &self.language {
+            settings.selected_language = language.clone();
+        }
+    }
+}
+
+/// Current settings with the overrides of the app profile matching the frontmost
+/// application, if any.
+fn settings_for_frontmost_app(app: &AppHandle) -> AppSettings {
+    let mut settings = get_settings(app);
+    if settings.app_profiles.is_empty() {
+        return settings;
+    }
 
-    if model.trim().is_empty() {
+    let Some(active_app) = active_app::frontmost_app() else {
+        debug!("Could not detect the frontmost application, using default settings");
+        return settings;
+    };
+    if let Some(profile) = settings.app_profile_for(&active_app).cloned() {
         debug!(
-            "Post-processing skipped because provider '{}' has no model configured",
-            provider.id
+            "Using app profile '{}' for frontmost app {:?}",
+            profile.name, active_app
         );
+        settings.apply_app_profile(&profile);
+    }
+    settings
+}
+
+/// Strip invisible Unicode characters that some LLMs (e.g., Qwen) may insert
+fn strip_invisible_chars(text: &str) -> String {
+    text.replace('\u{200B}', "") // Zero-Width Space
+        .replace('\u{200C}', "") // Zero-Width Non-Joiner
+        .replace('\u{200D}', "") // Zero-Width Joiner
+        .replace('\u{FEFF}', "") // Byte Order Mark / Zero-Width No-Break Space
+}
+
+/// Text produced by LLM post-processing and the provider that produced it.
+struct PostProcessOutput {
+    text: String,
+    provider_id: String,
+}
+
+/// Runs the prompt for `language` through the active provider, then through each
+/// configured fallback until one succeeds. With `stream`, the completion is requested
+/// as server-sent events and typed into the focused app while it arrives.
+async fn post_process_transcription(
+    settings: &AppSettings,
+    transcription: &str,
+    language: &str,
+    stream: Option<&StreamingPaste>,
+) -> Option<PostProcessOutput> {
+    let providers = settings.post_process_provider_chain();
+    if providers.is_empty() {
+        debug!("Post-processing enabled but no provider is selected");
         return None;
     }
 
-    let selected_prompt_id = match &settings.post_process_selected_prompt_id {
+    let selected_prompt_id = match settings.post_process_prompt_id(language) {
         Some(id) => id.clone(),
         None => {
             debug!("Post-processing skipped because no prompt is selected");
@@ -65,7 +159,7 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         .iter()
         .find(|prompt| prompt.id == selected_prompt_id)
     {
-        Some(prompt) => prompt.prompt.clone(),
+        Some(prompt) => prompt,
         None => {
             debug!(
                 "Post-processing skipped because prompt '{}' was not found",
@@ -75,53 +169,103 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         }
     };
 
-    if prompt.trim().is_empty() {
+    let has_system_prompt = prompt
+        .system_prompt
+        .as_deref()
+        .is_some_and(|s| !s.trim().is_empty());
+    if prompt.prompt.trim().is_empty() && !has_system_prompt {
         debug!("Post-processing skipped because the selected prompt is empty");
         return None;
     }
 
+    let messages = crate::llm_client::build_messages(prompt, transcription);
     debug!(
-        "Starting LLM post-processing with provider '{}' (model: {})",
-        provider.id, model
+        "Built {} messages ({} chars)",
+        messages.len(),
+        messages.iter().map(|m| m.content.len()).sum::<usize>()
     );
 
-    // Replace ${output} variable in the prompt with the actual text
-    let processed_prompt = prompt.replace("${output}", transcription);
-    debug!("Processed prompt length: {} chars", processed_prompt.len());
+    for provider in providers {
+        // Once streamed text has been typed, another provider would only duplicate it
+        if stream.is_some_and(|paste| paste.has_typed() || paste.is_cancelled()) {
+            break;
+        }
+
+        let model = settings
+            .post_process_models
+            .get(&provider.id)
+            .cloned()
+            .unwrap_or_default();
+
+        if model.trim().is_empty() {
+            debug!(
+                "Post-processing skipped provider '{}' because it has no model configured",
+                provider.id
+            );
+            continue;
+        }
+
+        debug!(
+            "Starting LLM post-processing with provider '{}' (model: {})",
+            provider.id, model
+        );
+
+        match run_post_process_provider(settings, provider, &model, messages.clone(), stream).await
+        {
+            Ok(Some(content)) => {
+                debug!(
+                    "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
+                    provider.id,
+                    content.len()
+                );
+                return Some(PostProcessOutput {
+                    text: content,
+                    provider_id: provider.id.clone(),
+                });
+            }
+            Ok(None) => {
+                error!(
+                    "LLM response from provider '{}' has no content",
+                    provider.id
+                );
+            }
+            Err(e) => {
+                error!(
+                    "LLM post-processing failed for provider '{}': {}",
+                    provider.id, e
+                );
+            }
+        }
+    }
+
+    error!("No post-processing provider succeeded. Falling back to original transcription.");
+    None
+}
 
+/// Send the messages to a single provider. Returns `Ok(None)` for an empty response.
+async fn run_post_process_provider(
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    messages: Vec<crate::llm_client::ChatMessage>,
+    stream: Option<&StreamingPaste>,
+) -> Result<Option<String>, String> {
     if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
         {
             if !apple_intelligence::check_apple_intelligence_availability() {
-                debug!("Apple Intelligence selected but not currently available on this device");
-                return None;
+                return Err("Apple Intelligence is not currently available on this device".into());
             }
 
             let token_limit = model.trim().parse::<i32>().unwrap_or(0);
-            return match apple_intelligence::process_text(&processed_prompt, token_limit) {
-                Ok(result) => {
-                    if result.trim().is_empty() {
-                        debug!("Apple Intelligence returned an empty response");
-                        None
-                    } else {
-                        debug!(
-                            "Apple Intelligence post-processing succeeded. Output length: {} chars",
-                            result.len()
-                        );
-                        Some(result)
-                    }
-                }
-                Err(err) => {
-                    error!("Apple Intelligence post-processing failed: {}", err);
-                    None
-                }
-            };
+            let processed_prompt = crate::llm_client::completion_prompt(&messages);
+            let result = apple_intelligence::process_text(&processed_prompt, token_limit)?;
+            return Ok(Some(result).filter(|r| !r.trim().is_empty()));
         }
 
         #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
         {
-            debug!("Apple Intelligence provider selected on unsupported platform");
-            return None;
+            return Err("Apple Intelligence is not supported on this platform".into());
         }
     }
 
@@ -132,54 +276,58 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         .unwrap_or_default();
 
     // Send the chat completion request
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
-    {
-        Ok(Some(content)) => {
-            // Strip invisible Unicode characters that some LLMs (e.g., Qwen) may insert
-            let content = content
-                .replace('\u{200B}', "") // Zero-Width Space
-                .replace('\u{200C}', "") // Zero-Width Non-Joiner
-                .replace('\u{200D}', "") // Zero-Width Joiner
-                .replace('\u{FEFF}', ""); // Byte Order Mark / Zero-Width No-Break Space
-            debug!(
-                "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
-                provider.id,
-                content.len()
-            );
-            Some(content)
-        }
-        Ok(None) => {
-            error!("LLM API response has no content");
-            None
-        }
-        Err(e) => {
-            error!(
-                "LLM post-processing failed for provider '{}': {}. Falling back to original transcription.",
-                provider.id,
-                e
-            );
-            None
+    let result = match stream {
+        Some(paste) => {
+            crate::llm_client::stream_chat_completion(provider, api_key, model, messages, |delta| {
+                paste.push(&strip_invisible_chars(delta))
+            })
+            .await
         }
+        None => crate::llm_client::send_chat_completion(provider, api_key, model, messages).await,
+    };
+
+    Ok(result?.map(|content| strip_invisible_chars(&content)))
+}
+
+/// The Chinese variant to convert to. In "auto" mode, Chinese text follows the
+/// system locale, e.g. `zh-TW` gets Traditional characters.
+fn target_chinese_variant(settings: &AppSettings, language: &str) -> Option<String> {
+    if settings.selected_language != "auto" {
+        return Some(settings.selected_language.clone());
+    }
+    if !language.starts_with("zh") {
+        return None;
+    }
+
+    let locale = tauri_plugin_os::locale()?;
+    if !locale.starts_with("zh") {
+        return None;
     }
+    let traditional = ["Hant", "TW", "HK", "MO"]
+        .iter()
+        .any(|marker| locale.contains(marker));
+    Some(if traditional { "zh-Hant" } else { "zh-Hans" }.to_string())
 }
 
 async fn maybe_convert_chinese_variant(
     settings: &AppSettings,
+    language: &str,
     transcription: &str,
 ) -> Option<String> {
+    let target = target_chinese_variant(settings, language).unwrap_or_default();
+
     // Check if language is set to Simplified or Traditional Chinese
-    let is_simplified = settings.selected_language == "zh-Hans";
-    let is_traditional = settings.selected_language == "zh-Hant";
+    let is_simplified = target == "zh-Hans";
+    let is_traditional = target == "zh-Hant";
 
     if !is_simplified && !is_traditional {
-        debug!("selected_language is not Simplified or Traditional Chinese; skipping translation");
+        debug!("Language is not Simplified or Traditional Chinese; skipping translation");
         return None;
     }
 
     debug!(
         "Starting Chinese translation using OpenCC for language: {}",
-        settings.selected_language
+        target
     );
 
     // Use OpenCC to convert based on selected language
@@ -208,6 +356,104 @@ async fn maybe_convert_chinese_variant(
     }
 }
 
+/// Result of running a raw transcription through the text pipeline.
+pub(crate) struct ProcessedTranscription {
+    /// Text to deliver to the user (pasted, printed, ...)
+    pub final_text: String,
+    /// Set when the final text differs from the raw transcription
+    pub post_processed_text: Option<String>,
+    /// The LLM prompt used, if post-processing ran
+    pub post_process_prompt: Option<String>,
+    /// The provider that produced the post-processed text, which may be a fallback
+    pub post_process_provider: Option<String>,
+}
+
+/// Apply Chinese variant conversion, optionally LLM post-processing, and snippet expansion
+/// to a transcription. Shared by the shortcut actions and the headless CLI so both produce
+/// identical output. `language` is the language the text is in, which in "auto" mode is
+/// the detected one.
+pub(crate) async fn process_transcription(
+    settings: &AppSettings,
+    transcription: &str,
+    language: &str,
+    post_process: bool,
+    stream: Option<&StreamingPaste>,
+) -> ProcessedTranscription {
+    let mut final_text = transcription.to_string();
+    let mut post_processed_text: Option<String> = None;
+    let mut post_process_prompt: Option<String> = None;
+    let mut post_process_provider: Option<String> = None;
+
+    // An utterance that is just a snippet trigger is pasted verbatim, skipping the LLM
+    if let Some(snippet) = snippets::whole_utterance(&settings.snippets, transcription) {
+        debug!("Expanding snippet '{}'", snippet.trigger);
+        return ProcessedTranscription {
+            final_text: snippet.content.clone(),
+            post_processed_text: Some(snippet.content.clone()),
+            post_process_prompt: None,
+            post_process_provider: None,
+        };
+    }
+
+    // First, check if Chinese variant conversion is needed
+    if let Some(converted_text) =
+        maybe_convert_chinese_variant(settings, language, transcription).await
+    {
+        final_text = converted_text;
+    }
+
+    // Then apply LLM post-processing if requested
+    // Uses final_text which may already have Chinese conversion applied
+    let processed = if post_process {
+        post_process_transcription(settings, &final_text, language, stream).await
+    } else {
+        None
+    };
+    if let Some(processed) = processed {
+        post_processed_text = Some(processed.text.clone());
+        final_text = processed.text;
+        post_process_provider = Some(processed.provider_id);
+
+        // Get the prompt that was used
+        if let Some(prompt_id) = settings.post_process_prompt_id(language) {
+            if let Some(prompt) = settings
+                .post_process_prompts
+                .iter()
+                .find(|p| &p.id == prompt_id)
+            {
+                // Prompts made of only a system message are recorded by that message
+                post_process_prompt = match &prompt.system_prompt {
+                    Some(system) if prompt.prompt.trim().is_empty() => Some(system.clone()),
+                    _ => Some(prompt.prompt.clone()),
+                };
+            }
+        }
+    } else if final_text != transcription {
+        // Chinese conversion was applied but no LLM post-processing
+        post_processed_text = Some(final_text.clone());
+    }
+
+    // Expand triggers last so snippets are pasted exactly as stored. Streamed output
+    // is already in the app, so it stays as typed.
+    let streamed = stream.is_some_and(StreamingPaste::has_typed);
+    let expanded = if streamed {
+        final_text.clone()
+    } else {
+        snippets::expand_snippets(&final_text, &settings.snippets)
+    };
+    if expanded != final_text {
+        final_text = expanded;
+        post_processed_text = Some(final_text.clone());
+    }
+
+    ProcessedTranscription {
+        final_text,
+        post_processed_text,
+        post_process_prompt,
+        post_process_provider,
+    }
+}
+
 impl ShortcutAction for TranscribeAction {
     fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
         let start_time = Instant::now();
@@ -215,7 +461,10 @@ impl ShortcutAction for TranscribeAction {
 
         // Load model in the background
         let tm = app.state::<Arc<TranscriptionManager>>();
-        tm.initiate_model_load();
+        match &self.model_id {
+            Some(model_id) => tm.initiate_model_load_for(model_id),
+            None => tm.initiate_model_load(),
+        }
 
         let binding_id = binding_id.to_string();
         change_tray_icon(app, TrayIconState::Recording);
@@ -228,6 +477,13 @@ impl ShortcutAction for TranscribeAction {
         let is_always_on = settings.always_on_microphone;
         debug!("Microphone mode - always_on: {}", is_always_on);
 
+        // Decode speech chunks while recording so only the tail is left at stop time.
+        // The session has to exist before the recorder emits its first chunk. It runs
+        // on the selected model, so bindings with their own model don't stream.
+        if settings.streaming_transcription && self.model_id.is_none() {
+            tm.start_streaming();
+        }
+
         let mut recording_started = false;
         if is_always_on {
             // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
@@ -267,6 +523,10 @@ impl ShortcutAction for TranscribeAction {
             }
         }
 
+        if !recording_started {
+            tm.cancel_streaming();
+        }
+
         if recording_started {
             // Dynamically register the cancel shortcut in a separate task to avoid deadlock
             shortcut::register_cancel_shortcut(app);
@@ -300,7 +560,7 @@ impl ShortcutAction for TranscribeAction {
         play_feedback_sound(app, SoundType::Stop);
 
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
-        let post_process = self.post_process;
+        let action = self.clone();
 
         tauri::async_runtime::spawn(async move {
             let binding_id = binding_id.clone(); // Clone for the inner async task
@@ -319,62 +579,132 @@ impl ShortcutAction for TranscribeAction {
 
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
+                let mut settings = settings_for_frontmost_app(&ah);
+                action.apply_overrides(&mut settings);
+                // Custom bindings only post-process while the feature is enabled
+                let post_process = action.post_process
+                    && (binding_id == "transcribe_with_post_process"
+                        || settings.post_process_enabled);
+                // Chunks decoded while recording used the global language; start over
+                // if the app profile or binding asks for a different one
+                let streamed_language = tm.is_streaming()
+                    && settings.selected_language == get_settings(&ah).selected_language;
+                let transcription_result = if streamed_language {
+                    tm.finish_streaming(&samples)
+                } else {
+                    tm.cancel_streaming();
+                    match &action.model_id {
+                        Some(model_id) => tm.transcribe_with_model(model_id, samples, &settings),
+                        None => tm.transcribe_with_settings(samples, &settings),
+                    }
+                };
+                match transcription_result {
                     Ok(transcription) => {
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
-                            transcription
+                            transcription.text
                         );
-                        if !transcription.is_empty() {
-                            let settings = get_settings(&ah);
-                            let mut final_text = transcription.clone();
-                            let mut post_processed_text: Option<String> = None;
-                            let mut post_process_prompt: Option<String> = None;
-
-                            // First, check if Chinese variant conversion is needed
-                            if let Some(converted_text) =
-                                maybe_convert_chinese_variant(&settings, &transcription).await
-                            {
-                                final_text = converted_text;
+                        // Anything that isn't a known command falls through to pasting.
+                        // Match the raw text so spoken punctuation can't eat the phrase.
+                        let voice_command = action
+                            .command_mode
+                            .then(|| {
+                                voice_commands::find_command(&settings, &transcription.raw_text)
+                            })
+                            .flatten()
+                            .cloned();
+                        if let Some(command) = voice_command {
+                            emit_transcription_complete(&ah, &binding_id, "", None);
+                            let ah_clone = ah.clone();
+                            ah.run_on_main_thread(move || {
+                                if let Err(e) = voice_commands::execute(&ah_clone, &command) {
+                                    error!("Failed to run voice command: {}", e);
+                                }
+                                utils::hide_recording_overlay(&ah_clone);
+                                change_tray_icon(&ah_clone, TrayIconState::Idle);
+                            })
+                            .unwrap_or_else(|e| {
+                                error!("Failed to run voice command on main thread: {:?}", e);
+                                utils::hide_recording_overlay(&ah);
+                                change_tray_icon(&ah, TrayIconState::Idle);
+                            });
+                        } else if !transcription.text.is_empty() {
+                            // Type the LLM output while it is generated; keep cancel available.
+                            // Snippets expand after the LLM, so their triggers can't be streamed.
+                            let streaming_paste = (post_process
+                                && settings.paste_method == PasteMethod::DirectStreaming
+                                && !snippets::contains_trigger(
+                                    &transcription.text,
+                                    &settings.snippets,
+                                ))
+                            .then(|| {
+                                shortcut::register_cancel_shortcut(&ah);
+                                StreamingPaste::begin(&ah)
+                            });
+
+                            let ProcessedTranscription {
+                                mut final_text,
+                                mut post_processed_text,
+                                post_process_prompt,
+                                post_process_provider,
+                            } = process_transcription(
+                                &settings,
+                                &transcription.text,
+                                transcription.language_or(&settings.selected_language),
+                                post_process,
+                                streaming_paste.as_ref(),
+                            )
+                            .await;
+
+                            if streaming_paste.is_some() {
+                                shortcut::unregister_cancel_shortcut(&ah);
+                            }
+                            let cancelled = streaming_paste
+                                .as_ref()
+                                .is_some_and(StreamingPaste::is_cancelled);
+                            // Every provider failed after part of the output was typed. That
+                            // part stays in the app, so it is also what gets reported and saved.
+                            let interrupted = streaming_paste.as_ref().filter(|paste| {
+                                !cancelled && paste.has_typed() && post_process_provider.is_none()
+                            });
+                            if let Some(paste) = interrupted {
+                                let typed = paste.abort();
+                                final_text = typed.clone();
+                                post_processed_text = Some(typed);
                             }
 
-                            // Then apply LLM post-processing if this is the post-process hotkey
-                            // Uses final_text which may already have Chinese conversion applied
-                            let processed = if post_process {
-                                post_process_transcription(&settings, &final_text).await
+                            if cancelled {
+                                emit_transcription_complete(
+                                    &ah,
+                                    &binding_id,
+                                    "",
+                                    Some("Cancelled".to_string()),
+                                );
+                            } else if interrupted.is_some() {
+                                emit_transcription_complete(
+                                    &ah,
+                                    &binding_id,
+                                    &final_text,
+                                    Some(
+                                        "Post-processing failed partway, only part of the text was typed"
+                                            .to_string(),
+                                    ),
+                                );
                             } else {
-                                None
-                            };
-                            if let Some(processed_text) = processed {
-                                post_processed_text = Some(processed_text.clone());
-                                final_text = processed_text;
-
-                                // Get the prompt that was used
-                                if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                    if let Some(prompt) = settings
-                                        .post_process_prompts
-                                        .iter()
-                                        .find(|p| &p.id == prompt_id)
-                                    {
-                                        post_process_prompt = Some(prompt.prompt.clone());
-                                    }
-                                }
-                            } else if final_text != transcription {
-                                // Chinese conversion was applied but no LLM post-processing
-                                post_processed_text = Some(final_text.clone());
+                                emit_transcription_complete(&ah, &binding_id, &final_text, None);
                             }
 
                             // Save to history with post-processed text and prompt
                             let hm_clone = Arc::clone(&hm);
-                            let transcription_for_history = transcription.clone();
                             tauri::async_runtime::spawn(async move {
                                 if let Err(e) = hm_clone
                                     .save_transcription(
                                         samples_clone,
-                                        transcription_for_history,
+                                        transcription,
                                         post_processed_text,
                                         post_process_prompt,
+                                        post_process_provider,
                                     )
                                     .await
                                 {
@@ -382,39 +712,75 @@ impl ShortcutAction for TranscribeAction {
                                 }
                             });
 
-                            // Paste the final text (either processed or original)
-                            let ah_clone = ah.clone();
-                            let paste_time = Instant::now();
-                            ah.run_on_main_thread(move || {
-                                match utils::paste(final_text, ah_clone.clone()) {
-                                    Ok(()) => debug!(
-                                        "Text pasted successfully in {:?}",
-                                        paste_time.elapsed()
-                                    ),
-                                    Err(e) => error!("Failed to paste transcription: {}", e),
+                            if cancelled {
+                                debug!(
+                                    "Streamed post-processing was cancelled, nothing more to paste"
+                                );
+                            } else if interrupted.is_some() {
+                                debug!("Streamed post-processing failed, nothing more to paste");
+                                utils::hide_recording_overlay(&ah);
+                                change_tray_icon(&ah, TrayIconState::Idle);
+                            } else if let Some(paste) =
+                                streaming_paste.as_ref().filter(|p| p.has_typed())
+                            {
+                                let suffix = if settings.append_trailing_space {
+                                    " "
+                                } else {
+                                    ""
+                                };
+                                paste.finish(suffix);
+                                if settings.clipboard_handling == ClipboardHandling::CopyToClipboard
+                                {
+                                    if let Err(e) = ah.clipboard().write_text(&final_text) {
+                                        error!("Failed to copy to clipboard: {}", e);
+                                    }
                                 }
-                                // Hide the overlay after transcription is complete
-                                utils::hide_recording_overlay(&ah_clone);
-                                change_tray_icon(&ah_clone, TrayIconState::Idle);
-                            })
-                            .unwrap_or_else(|e| {
-                                error!("Failed to run paste on main thread: {:?}", e);
                                 utils::hide_recording_overlay(&ah);
                                 change_tray_icon(&ah, TrayIconState::Idle);
-                            });
+                            } else {
+                                // Paste the final text (either processed or original)
+                                let ah_clone = ah.clone();
+                                let paste_time = Instant::now();
+                                let paste_settings = settings.clone();
+                                ah.run_on_main_thread(move || {
+                                    match utils::paste(
+                                        final_text,
+                                        ah_clone.clone(),
+                                        &paste_settings,
+                                    ) {
+                                        Ok(()) => debug!(
+                                            "Text pasted successfully in {:?}",
+                                            paste_time.elapsed()
+                                        ),
+                                        Err(e) => error!("Failed to paste transcription: {}", e),
+                                    }
+                                    // Hide the overlay after transcription is complete
+                                    utils::hide_recording_overlay(&ah_clone);
+                                    change_tray_icon(&ah_clone, TrayIconState::Idle);
+                                })
+                                .unwrap_or_else(|e| {
+                                    error!("Failed to run paste on main thread: {:?}", e);
+                                    utils::hide_recording_overlay(&ah);
+                                    change_tray_icon(&ah, TrayIconState::Idle);
+                                });
+                            }
                         } else {
+                            emit_transcription_complete(&ah, &binding_id, "", None);
                             utils::hide_recording_overlay(&ah);
                             change_tray_icon(&ah, TrayIconState::Idle);
                         }
                     }
                     Err(err) => {
                         debug!("Global Shortcut Transcription error: {}", err);
+                        emit_transcription_complete(&ah, &binding_id, "", Some(err.to_string()));
                         utils::hide_recording_overlay(&ah);
                         change_tray_icon(&ah, TrayIconState::Idle);
                     }
                 }
             } else {
                 debug!("No samples retrieved from recording stop");
+                tm.cancel_streaming();
+                emit_transcription_complete(&ah, &binding_id, "", None);
                 utils::hide_recording_overlay(&ah);
                 change_tray_icon(&ah, TrayIconState::Idle);
             }
@@ -468,18 +834,25 @@ impl ShortcutAction for TestAction {
     }
 }
 
-// Static Action Map
-pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::new(|| {
+fn builtin_actions() -> HashMap<String, Arc<dyn ShortcutAction>> {
     let mut map = HashMap::new();
     map.insert(
         "transcribe".to_string(),
+        Arc::new(TranscribeAction::default()) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "transcribe_with_post_process".to_string(),
         Arc::new(TranscribeAction {
-            post_process: false,
+            post_process: true,
+            ..Default::default()
         }) as Arc<dyn ShortcutAction>,
     );
     map.insert(
-        "transcribe_with_post_process".to_string(),
-        Arc::new(TranscribeAction { post_process: true }) as Arc<dyn ShortcutAction>,
+        "voice_command".to_string(),
+        Arc::new(TranscribeAction {
+            command_mode: true,
+            ..Default::default()
+        }) as Arc<dyn ShortcutAction>,
     );
     map.insert(
         "cancel".to_string(),
@@ -490,4 +863,43 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
     );
     map
-});
+}
+
+// Action Map, rebuilt whenever the bindings change
+pub static ACTION_MAP: Lazy<RwLock<HashMap<String, Arc<dyn ShortcutAction>>>> =
+    Lazy::new(|| RwLock::new(builtin_actions()));
+
+pub fn action_for(binding_id: &str) -> Option<Arc<dyn ShortcutAction>> {
+    ACTION_MAP.read().unwrap().get(binding_id).cloned()
+}
+
+// Built-in bindings that record and transcribe
+const TRANSCRIBE_BINDINGS: [&str; 3] = [
+    "transcribe",
+    "transcribe_with_post_process",
+    "voice_command",
+];
+
+/// Whether the binding records and transcribes, as opposed to actions such as `cancel`.
+pub fn is_transcribe_binding(settings: &AppSettings, binding_id: &str) -> bool {
+    TRANSCRIBE_BINDINGS.contains(&binding_id)
+        || settings
+            .custom_bindings()
+            .iter()
+            .any(|binding| binding.id == binding_id)
+}
+
+/// Give every transcribe binding an action configured with its overrides.
+pub fn refresh_actions(settings: &AppSettings) {
+    let mut map = builtin_actions();
+    let builtin = TRANSCRIBE_BINDINGS
+        .iter()
+        .filter_map(|id| settings.bindings.get(*id).cloned());
+    for binding in builtin.chain(settings.custom_bindings()) {
+        map.insert(
+            binding.id.clone(),
+            Arc::new(TranscribeAction::for_binding(&binding)) as Arc<dyn ShortcutAction>,
+        );
+    }
+    *ACTION_MAP.write().unwrap() = map;
+}
@@ -16,6 +16,12 @@ use crate::audio_toolkit::{
     VoiceActivityDetector,
 };
 
+/// Minimum amount of speech (in 16 kHz samples) before a chunk is handed to
+/// the speech-chunk callback. Shorter segments are merged with the next one.
+const MIN_SPEECH_CHUNK_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+type SamplesCallback = Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>;
+
 enum Cmd {
     Start,
     Stop(mpsc::Sender<Vec<f32>>),
@@ -27,7 +33,8 @@ pub struct AudioRecorder {
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SamplesCallback>,
+    chunk_cb: Option<SamplesCallback>,
 }
 
 impl AudioRecorder {
@@ -38,6 +45,7 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            chunk_cb: None,
         })
     }
 
@@ -54,6 +62,18 @@ impl AudioRecorder {
         self
     }
 
+    /// Registers a callback that receives each VAD-delimited speech chunk while
+    /// recording. A chunk is emitted once speech is followed by silence, so the
+    /// concatenation of all chunks is always a prefix of the samples returned
+    /// by [`AudioRecorder::stop`].
+    pub fn with_speech_chunk_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(Vec<f32>) + Send + Sync + 'static,
+    {
+        self.chunk_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -72,8 +92,9 @@ impl AudioRecorder {
 
         let thread_device = device.clone();
         let vad = self.vad.clone();
-        // Move the optional level callback into the worker thread
+        // Move the optional level and speech-chunk callbacks into the worker thread
         let level_cb = self.level_cb.clone();
+        let chunk_cb = self.chunk_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -117,7 +138,7 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb, chunk_cb);
             // stream is dropped here, after run_consumer returns
         });
 
@@ -244,7 +265,8 @@ fn run_consumer(
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SamplesCallback>,
+    chunk_cb: Option<SamplesCallback>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -255,6 +277,11 @@ fn run_consumer(
     let mut processed_samples = Vec::<f32>::new();
     let mut recording = false;
 
+    // ---------- speech chunking ------------------------------------------ //
+    // `chunk_start` marks the end of the audio already handed to `chunk_cb`.
+    let mut chunk_start = 0usize;
+    let mut was_speech = false;
+
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
     const WINDOW_SIZE: usize = 512;
@@ -271,19 +298,38 @@ fn run_consumer(
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(buf);
+                    true
+                }
+                VadFrame::Noise => false,
             }
         } else {
             out_buf.extend_from_slice(samples);
+            true
+        }
+    }
+
+    fn maybe_emit_chunk(
+        samples: &[f32],
+        chunk_start: &mut usize,
+        chunk_cb: &Option<SamplesCallback>,
+    ) {
+        let Some(cb) = chunk_cb else {
+            return;
+        };
+
+        if samples.len() - *chunk_start >= MIN_SPEECH_CHUNK_SAMPLES {
+            cb(samples[*chunk_start..].to_vec());
+            *chunk_start = samples.len();
         }
     }
 
@@ -302,7 +348,13 @@ fn run_consumer(
 
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+            let is_speech = handle_frame(frame, recording, &vad, &mut processed_samples);
+
+            // Speech followed by silence closes the current chunk
+            if recording && was_speech && !is_speech {
+                maybe_emit_chunk(&processed_samples, &mut chunk_start, &chunk_cb);
+            }
+            was_speech = is_speech;
         });
 
         // non-blocking check for a command
@@ -310,6 +362,8 @@ fn run_consumer(
             match cmd {
                 Cmd::Start => {
                     processed_samples.clear();
+                    chunk_start = 0;
+                    was_speech = false;
                     recording = true;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
@@ -321,7 +375,7 @@ fn run_consumer(
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        handle_frame(frame, true, &vad, &mut processed_samples);
                     });
 
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
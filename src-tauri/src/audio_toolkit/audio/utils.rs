@@ -1,7 +1,14 @@
 use anyhow::Result;
 use hound::{WavSpec, WavWriter};
 use log::debug;
+use rodio::{Decoder, Source};
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
+use std::time::Duration;
+
+use super::FrameResampler;
+use crate::audio_toolkit::constants;
 
 /// Save audio samples as a WAV file
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
@@ -24,3 +31,37 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Decode an audio file (WAV, FLAC, ...) into 16kHz mono samples ready for transcription
+pub fn load_audio_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    let file = File::open(file_path.as_ref())?;
+    let decoder = Decoder::new(BufReader::new(file))?;
+
+    let channels = decoder.channels().max(1) as usize;
+    let sample_rate = decoder.sample_rate() as usize;
+    let interleaved: Vec<f32> = decoder.collect();
+
+    // Downmix to mono by averaging channels
+    let mono: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let mut resampler = FrameResampler::new(
+        sample_rate,
+        constants::WHISPER_SAMPLE_RATE as usize,
+        Duration::from_millis(30),
+    );
+    let mut samples = Vec::with_capacity(mono.len());
+    resampler.push(&mono, |frame| samples.extend_from_slice(frame));
+    resampler.finish(|frame| samples.extend_from_slice(frame));
+
+    debug!(
+        "Loaded audio file {:?}: {} Hz, {} channel(s), {} samples after resampling",
+        file_path.as_ref(),
+        sample_rate,
+        channels,
+        samples.len()
+    );
+    Ok(samples)
+}
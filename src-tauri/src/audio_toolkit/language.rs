@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+/// The language a transcription appears to be in, guessed from its text in
+/// "auto" mode. This is not the language the speech model detected.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct DetectedLanguage {
+    /// Code in the same form as `selected_language`, e.g. `en`, `de` or `zh-Hant`
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Latin,
+    Han,
+    Kana,
+    Hangul,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Thai,
+    Devanagari,
+}
+
+fn script_of(c: char) -> Option<Script> {
+    let script = match c as u32 {
+        0x0041..=0x024F => Script::Latin,
+        0x0370..=0x03FF => Script::Greek,
+        0x0400..=0x04FF => Script::Cyrillic,
+        0x0590..=0x05FF => Script::Hebrew,
+        0x0600..=0x06FF => Script::Arabic,
+        0x0900..=0x097F => Script::Devanagari,
+        0x0E00..=0x0E7F => Script::Thai,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+        0x3040..=0x30FF => Script::Kana,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF => Script::Han,
+        _ => return None,
+    };
+    Some(script)
+}
+
+/// Common short words that, together, tell Latin-script languages apart
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "you", "that", "this", "with", "have", "it", "of", "to",
+            "was", "what", "for", "not",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "sie", "ein", "eine", "mit", "auf",
+            "zu", "den", "wir", "auch",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "je", "vous", "une", "des", "pas", "que", "dans",
+            "pour", "nous", "ce", "avec",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "es", "que", "de", "no", "una", "por", "con", "para",
+            "pero", "está", "muy",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "la", "che", "è", "di", "e", "non", "per", "sono", "una", "con", "ma", "anche",
+            "questo", "gli", "mi",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "que", "é", "de", "não", "uma", "com", "para", "em", "mas", "você",
+            "isso", "está", "muito",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "niet", "ik", "je", "dat", "van", "op", "zijn", "met",
+            "voor", "maar", "ook",
+        ],
+    ),
+];
+
+/// Stopwords the winning language needs before a Latin-script guess is made
+const MIN_STOPWORD_HITS: usize = 2;
+/// Share of the words that have to be stopwords, so languages without a list
+/// aren't matched on a few words they happen to share with one
+const MIN_STOPWORD_COVERAGE: f32 = 0.15;
+/// How far the winner has to lead the runner-up, as a share of the recognised
+/// stopwords
+const MIN_LATIN_CONFIDENCE: f32 = 0.5;
+
+/// Characters written differently in Simplified and Traditional Chinese
+const SIMPLIFIED_ONLY: &str = "这们个来说为会时国对学还后发开过么与点没";
+const TRADITIONAL_ONLY: &str = "這們個來說為會時國對學還後發開過麼與點沒";
+
+/// Guesses the language of transcribed text.
+///
+/// transcribe-rs does not report the language Whisper picked in "auto" mode, so it
+/// is guessed from the output instead: the writing system decides most languages,
+/// and common short words tell Latin-script languages apart. The guess describes
+/// the text, which differs from the spoken language when Whisper translates.
+///
+/// # Arguments
+/// * `text` - The transcription text
+///
+/// # Returns
+/// The detected language, or `None` when the text gives no clear signal
+pub fn detect_language(text: &str) -> Option<DetectedLanguage> {
+    let mut counts: HashMap<Script, usize> = HashMap::new();
+    for script in text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .filter_map(script_of)
+    {
+        *counts.entry(script).or_default() += 1;
+    }
+    // Japanese mixes kanji with kana, so kanji count towards it once kana appear
+    if counts.contains_key(&Script::Kana) {
+        let han = counts.remove(&Script::Han).unwrap_or(0);
+        *counts.entry(Script::Kana).or_default() += han;
+    }
+
+    let (&script, _) = counts.iter().max_by_key(|(_, count)| **count)?;
+
+    let code = match script {
+        Script::Latin => return detect_latin(text),
+        Script::Han => chinese_variant(text),
+        Script::Kana => "ja",
+        Script::Hangul => "ko",
+        Script::Cyrillic if text.contains(['і', 'ї', 'є', 'ґ']) => "uk",
+        Script::Cyrillic => "ru",
+        Script::Greek => "el",
+        Script::Arabic => "ar",
+        Script::Hebrew => "he",
+        Script::Thai => "th",
+        Script::Devanagari => "hi",
+    };
+    Some(DetectedLanguage {
+        code: code.to_string(),
+    })
+}
+
+fn detect_latin(text: &str) -> Option<DetectedLanguage> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .collect();
+
+    let is_stopword = |word: &String| {
+        STOPWORDS
+            .iter()
+            .any(|(_, list)| list.contains(&word.as_str()))
+    };
+    let recognised = words.iter().filter(|w| is_stopword(w)).count();
+    if recognised == 0 || (recognised as f32) < words.len() as f32 * MIN_STOPWORD_COVERAGE {
+        return None;
+    }
+
+    let mut scores: Vec<(&str, usize)> = STOPWORDS
+        .iter()
+        .map(|(code, list)| {
+            let hits = words.iter().filter(|w| list.contains(&w.as_str())).count();
+            (*code, hits)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+    let (code, hits) = scores[0];
+    let runner_up = scores[1].1;
+
+    // Ties and thin leads are left to the user's language setting
+    let confidence = (hits - runner_up) as f32 / recognised as f32;
+    if hits < MIN_STOPWORD_HITS || confidence < MIN_LATIN_CONFIDENCE {
+        return None;
+    }
+
+    Some(DetectedLanguage {
+        code: code.to_string(),
+    })
+}
+
+fn chinese_variant(text: &str) -> &'static str {
+    let simplified = text
+        .chars()
+        .filter(|c| SIMPLIFIED_ONLY.contains(*c))
+        .count();
+    let traditional = text
+        .chars()
+        .filter(|c| TRADITIONAL_ONLY.contains(*c))
+        .count();
+    if traditional > simplified {
+        "zh-Hant"
+    } else {
+        "zh-Hans"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(text: &str) -> Option<String> {
+        detect_language(text).map(|language| language.code)
+    }
+
+    #[test]
+    fn detects_latin_languages_from_common_words() {
+        assert_eq!(
+            code("What is the plan for this week?").as_deref(),
+            Some("en")
+        );
+        assert_eq!(
+            code("Ich weiß nicht, ob das stimmt.").as_deref(),
+            Some("de")
+        );
+        assert_eq!(code("Lui ha detto che non è vero.").as_deref(), Some("it"));
+        assert_eq!(code("Pero no es para mañana.").as_deref(), Some("es"));
+        assert_eq!(code("Hmm."), None);
+    }
+
+    #[test]
+    fn ambiguous_latin_text_is_not_guessed() {
+        // "que" is a stopword in French, Spanish and Portuguese alike
+        assert_eq!(code("ha dicho que sí"), None);
+        // Polish shares only "to" with the English list
+        assert_eq!(code("to jest bardzo dobre rozwiązanie"), None);
+        // French and Spanish tie on "la" and "que"
+        assert_eq!(code("la casa que vimos"), None);
+    }
+
+    #[test]
+    fn detects_languages_by_script() {
+        assert_eq!(
+            code("今天天气很好，我们去公园吧").as_deref(),
+            Some("zh-Hans")
+        );
+        assert_eq!(
+            code("今天天氣很好，我們去公園吧").as_deref(),
+            Some("zh-Hant")
+        );
+        assert_eq!(code("今日はいい天気ですね").as_deref(), Some("ja"));
+        assert_eq!(code("안녕하세요").as_deref(), Some("ko"));
+        assert_eq!(code("Привет, как дела?").as_deref(), Some("ru"));
+    }
+}
@@ -1,12 +1,24 @@
 pub mod audio;
 pub mod constants;
+pub mod language;
+pub mod punctuation;
+pub mod replacements;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    list_input_devices, list_output_devices, load_audio_file, save_wav_file, AudioRecorder,
+    CpalDeviceInfo,
+};
+pub use language::{detect_language, DetectedLanguage};
+pub use punctuation::{apply_spoken_punctuation, builtin_tokens, PunctuationSpacing, SpokenToken};
+pub use replacements::{
+    apply_replacement_rules, CompiledReplacements, ReplacementCase, ReplacementRule,
+};
+pub use text::{
+    apply_custom_words, builtin_filler_words, filter_transcription_output, match_phrase,
+    phrase_matches_exactly, TranscriptionFilter,
 };
-pub use text::{apply_custom_words, filter_transcription_output};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
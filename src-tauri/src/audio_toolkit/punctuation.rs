@@ -0,0 +1,369 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use PunctuationSpacing::{AttachLeft, AttachRight, Joined};
+
+/// How a spoken token's replacement is spaced against its neighbours
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PunctuationSpacing {
+    /// Attaches to the previous word, e.g. `,` or `)`
+    AttachLeft,
+    /// Attaches to the next word, e.g. `(`
+    AttachRight,
+    /// Spaces on both sides, e.g. `&`
+    Spaced,
+    /// No spaces on either side, e.g. line breaks
+    Joined,
+}
+
+/// A spoken phrase and the text it is written as
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct SpokenToken {
+    pub phrase: String,
+    pub replacement: String,
+    pub spacing: PunctuationSpacing,
+    /// Only replace the phrase at a clause boundary, for words that are also
+    /// ordinary vocabulary such as "period" or "punto"
+    #[serde(default)]
+    pub boundary_only: bool,
+}
+
+impl SpokenToken {
+    fn new(phrase: &str, replacement: &str, spacing: PunctuationSpacing) -> Self {
+        SpokenToken {
+            phrase: phrase.to_string(),
+            replacement: replacement.to_string(),
+            spacing,
+            boundary_only: BOUNDARY_ONLY.contains(&phrase),
+        }
+    }
+}
+
+type Vocabulary = &'static [(&'static str, &'static str, PunctuationSpacing)];
+
+/// Built-in phrases that are also common words ("the trial period", "el punto de
+/// vista"). Adding one as a custom token replaces it everywhere again.
+const BOUNDARY_ONLY: &[&str] = &["period", "punkt", "point", "punto", "coma"];
+
+const EN_PUNCTUATION: Vocabulary = &[
+    ("comma", ",", AttachLeft),
+    ("period", ".", AttachLeft),
+    ("full stop", ".", AttachLeft),
+    ("question mark", "?", AttachLeft),
+    ("exclamation mark", "!", AttachLeft),
+    ("exclamation point", "!", AttachLeft),
+    ("colon", ":", AttachLeft),
+    ("semicolon", ";", AttachLeft),
+    ("ellipsis", "...", AttachLeft),
+    ("open paren", "(", AttachRight),
+    ("close paren", ")", AttachLeft),
+    ("open parenthesis", "(", AttachRight),
+    ("close parenthesis", ")", AttachLeft),
+    ("open bracket", "[", AttachRight),
+    ("close bracket", "]", AttachLeft),
+    ("open quote", "\"", AttachRight),
+    ("close quote", "\"", AttachLeft),
+];
+
+const EN_FORMATTING: Vocabulary = &[
+    ("new line", "\n", Joined),
+    ("newline", "\n", Joined),
+    ("new paragraph", "\n\n", Joined),
+    ("bullet point", "\n- ", Joined),
+];
+
+const DE_PUNCTUATION: Vocabulary = &[
+    ("komma", ",", AttachLeft),
+    ("punkt", ".", AttachLeft),
+    ("fragezeichen", "?", AttachLeft),
+    ("ausrufezeichen", "!", AttachLeft),
+    ("doppelpunkt", ":", AttachLeft),
+    ("semikolon", ";", AttachLeft),
+    ("klammer auf", "(", AttachRight),
+    ("klammer zu", ")", AttachLeft),
+    ("anführungszeichen unten", "„", AttachRight),
+    ("anführungszeichen oben", "“", AttachLeft),
+];
+
+const DE_FORMATTING: Vocabulary = &[
+    ("neue zeile", "\n", Joined),
+    ("neuer absatz", "\n\n", Joined),
+    ("aufzählungspunkt", "\n- ", Joined),
+];
+
+const FR_PUNCTUATION: Vocabulary = &[
+    ("virgule", ",", AttachLeft),
+    ("point", ".", AttachLeft),
+    ("point d'interrogation", " ?", AttachLeft),
+    ("point d'exclamation", " !", AttachLeft),
+    ("deux points", " :", AttachLeft),
+    ("point-virgule", " ;", AttachLeft),
+    ("ouvrir la parenthèse", "(", AttachRight),
+    ("fermer la parenthèse", ")", AttachLeft),
+];
+
+const FR_FORMATTING: Vocabulary = &[
+    ("à la ligne", "\n", Joined),
+    ("nouvelle ligne", "\n", Joined),
+    ("nouveau paragraphe", "\n\n", Joined),
+    ("puce", "\n- ", Joined),
+];
+
+const ES_PUNCTUATION: Vocabulary = &[
+    ("coma", ",", AttachLeft),
+    ("punto", ".", AttachLeft),
+    ("punto y coma", ";", AttachLeft),
+    ("dos puntos", ":", AttachLeft),
+    ("signo de interrogación", "?", AttachLeft),
+    ("signo de exclamación", "!", AttachLeft),
+    ("abrir paréntesis", "(", AttachRight),
+    ("cerrar paréntesis", ")", AttachLeft),
+];
+
+const ES_FORMATTING: Vocabulary = &[
+    ("nueva línea", "\n", Joined),
+    ("nuevo párrafo", "\n\n", Joined),
+    ("viñeta", "\n- ", Joined),
+];
+
+/// The built-in vocabulary for a language code such as `en`, `de-AT` or `auto`.
+/// Unknown languages, including `auto`, use English.
+pub fn builtin_tokens(language: &str, formatting: bool) -> Vec<SpokenToken> {
+    let base = language.split(['-', '_']).next().unwrap_or(language);
+    let (punctuation, layout) = match base.to_lowercase().as_str() {
+        "de" => (DE_PUNCTUATION, DE_FORMATTING),
+        "fr" => (FR_PUNCTUATION, FR_FORMATTING),
+        "es" => (ES_PUNCTUATION, ES_FORMATTING),
+        _ => (EN_PUNCTUATION, EN_FORMATTING),
+    };
+
+    let layout: Vocabulary = if formatting { layout } else { &[] };
+    punctuation
+        .iter()
+        .chain(layout)
+        .map(|(phrase, replacement, spacing)| SpokenToken::new(phrase, replacement, *spacing))
+        .collect()
+}
+
+/// Lowercase a word and strip the punctuation the model may have put around it
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+enum Piece<'a> {
+    Word(&'a str),
+    Token(&'a SpokenToken),
+}
+
+/// Replaces spoken punctuation and formatting tokens with the text they stand for.
+///
+/// Tokens are matched as whole words, ignoring case and any punctuation the model
+/// already added around them. Longer phrases win, so "punto y coma" beats "punto".
+/// Boundary-only tokens are kept as words unless they end a clause.
+/// The word after a spoken sentence end or line break is capitalized.
+///
+/// # Arguments
+/// * `text` - The transcription text
+/// * `tokens` - Vocabulary to apply, earlier entries win between phrases of equal length
+///
+/// # Returns
+/// The text with spoken tokens replaced
+pub fn apply_spoken_punctuation(text: &str, tokens: &[SpokenToken]) -> String {
+    if tokens.is_empty() {
+        return text.to_string();
+    }
+
+    let mut phrases: Vec<(Vec<String>, &SpokenToken)> = tokens
+        .iter()
+        .filter(|token| !token.phrase.trim().is_empty())
+        .map(|token| {
+            let words = token
+                .phrase
+                .split_whitespace()
+                .map(normalize_word)
+                .collect();
+            (words, token)
+        })
+        .collect();
+    // Stable sort keeps user tokens ahead of built-in ones of the same length
+    phrases.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|w| normalize_word(w)).collect();
+
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let matched = phrases.iter().find(|(phrase, token)| {
+            normalized
+                .get(i..i + phrase.len())
+                .is_some_and(|candidate| candidate == phrase.as_slice())
+                && (!token.boundary_only || ends_clause(&words, i + phrase.len()))
+        });
+        match matched {
+            Some((phrase, token)) => {
+                pieces.push(Piece::Token(token));
+                i += phrase.len();
+            }
+            None => {
+                pieces.push(Piece::Word(words[i]));
+                i += 1;
+            }
+        }
+    }
+
+    join_pieces(&pieces)
+}
+
+/// Whether the phrase ending before `words[end]` closes a clause: it ends the text,
+/// the model put punctuation after it, or the next word starts a new sentence.
+fn ends_clause(words: &[&str], end: usize) -> bool {
+    let Some(next) = words.get(end) else {
+        return true;
+    };
+    let last = words[end - 1];
+    last.ends_with(|c: char| !c.is_alphanumeric()) || next.starts_with(char::is_uppercase)
+}
+
+fn join_pieces(pieces: &[Piece]) -> String {
+    let mut out = String::new();
+    // The previous token doesn't want a space after it
+    let mut glue_next = false;
+    let mut capitalize_next = false;
+
+    for piece in pieces {
+        match piece {
+            Piece::Word(word) => {
+                if !out.is_empty() && !glue_next {
+                    out.push(' ');
+                }
+                if capitalize_next {
+                    out.push_str(&capitalize(word));
+                } else {
+                    out.push_str(word);
+                }
+                glue_next = false;
+                capitalize_next = false;
+            }
+            Piece::Token(token) => {
+                match token.spacing {
+                    AttachLeft | Joined => {
+                        out.truncate(out.trim_end_matches(' ').len());
+                        // Drop punctuation the model added before a spoken one
+                        if token.spacing == AttachLeft
+                            && token
+                                .replacement
+                                .trim_start()
+                                .starts_with(is_sentence_punctuation)
+                        {
+                            out.truncate(out.trim_end_matches(is_sentence_punctuation).len());
+                        }
+                    }
+                    _ => {
+                        if !out.is_empty() && !glue_next {
+                            out.push(' ');
+                        }
+                    }
+                }
+                out.push_str(&token.replacement);
+                glue_next = matches!(token.spacing, AttachRight | Joined);
+                let end = token.replacement.trim_end_matches(' ');
+                capitalize_next = token.replacement.contains('\n')
+                    || end.ends_with(['.', '!', '?'])
+                    || (capitalize_next && token.spacing == AttachRight);
+            }
+        }
+    }
+
+    out.trim_matches(' ').to_string()
+}
+
+fn is_sentence_punctuation(c: char) -> bool {
+    matches!(c, ',' | '.' | ';' | ':' | '!' | '?')
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en() -> Vec<SpokenToken> {
+        builtin_tokens("en", true)
+    }
+
+    #[test]
+    fn replaces_spoken_punctuation() {
+        assert_eq!(
+            apply_spoken_punctuation("hello comma world period How are you question mark", &en()),
+            "hello, world. How are you?"
+        );
+    }
+
+    #[test]
+    fn ambiguous_words_only_end_clauses() {
+        assert_eq!(
+            apply_spoken_punctuation("the trial period ends today period", &en()),
+            "the trial period ends today."
+        );
+        let es = builtin_tokens("es", true);
+        assert_eq!(
+            apply_spoken_punctuation("desde mi punto de vista, coma, todo bien punto", &es),
+            "desde mi punto de vista, todo bien."
+        );
+
+        // A custom token opts the word back in everywhere
+        let mut tokens = vec![SpokenToken::new("period", ".", AttachLeft)];
+        tokens[0].boundary_only = false;
+        tokens.extend(en());
+        assert_eq!(
+            apply_spoken_punctuation("one period two", &tokens),
+            "one. Two"
+        );
+    }
+
+    #[test]
+    fn ignores_punctuation_added_by_the_model() {
+        assert_eq!(
+            apply_spoken_punctuation("Hello, comma, world. Period.", &en()),
+            "Hello, world."
+        );
+    }
+
+    #[test]
+    fn handles_brackets_and_formatting() {
+        assert_eq!(
+            apply_spoken_punctuation(
+                "call foo open paren bar close paren new line bullet point first item",
+                &en()
+            ),
+            "call foo (bar)\n\n- First item"
+        );
+    }
+
+    #[test]
+    fn formatting_can_be_disabled() {
+        let tokens = builtin_tokens("en", false);
+        assert_eq!(
+            apply_spoken_punctuation("one new line two", &tokens),
+            "one new line two"
+        );
+    }
+
+    #[test]
+    fn prefers_longer_phrases_and_uses_locale() {
+        let tokens = builtin_tokens("es-MX", true);
+        assert_eq!(
+            apply_spoken_punctuation("uno punto y coma dos punto", &tokens),
+            "uno; dos."
+        );
+        assert_eq!(builtin_tokens("auto", true), en());
+    }
+}
@@ -0,0 +1,216 @@
+use super::text::preserve_case_pattern;
+use log::warn;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// How a replacement rule treats letter case
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementCase {
+    /// Match the exact case
+    Sensitive,
+    /// Match any case and insert the replacement as written
+    #[default]
+    Insensitive,
+    /// Match any case and give the replacement the case of the matched text
+    Preserve,
+}
+
+/// A find/replace pair applied to every transcription
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct ReplacementRule {
+    pub find: String,
+    pub replace: String,
+    /// Treat `find` as a regular expression; `replace` may then use `$1` or `${name}`
+    #[serde(default)]
+    pub is_regex: bool,
+    #[serde(default)]
+    pub case: ReplacementCase,
+    /// Only match whole words
+    #[serde(default = "crate::utils::default_true")]
+    pub whole_word: bool,
+    #[serde(default = "crate::utils::default_true")]
+    pub enabled: bool,
+}
+
+impl ReplacementRule {
+    /// Compile the rule's pattern, reporting invalid regular expressions.
+    pub fn compile(&self) -> Result<Regex, String> {
+        if self.find.is_empty() {
+            return Err("Replacement rule has an empty pattern".to_string());
+        }
+
+        let pattern = if self.is_regex {
+            self.find.clone()
+        } else {
+            regex::escape(&self.find)
+        };
+        let pattern = if !self.whole_word {
+            pattern
+        } else if self.is_regex {
+            format!(r"\b(?:{})\b", pattern)
+        } else {
+            // `\b` only makes sense next to word characters, e.g. not around "C++"
+            let boundary = |c: Option<char>| match c {
+                Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                _ => "",
+            };
+            let first = boundary(self.find.chars().next());
+            let last = boundary(self.find.chars().last());
+            format!("{}{}{}", first, pattern, last)
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(self.case != ReplacementCase::Sensitive)
+            .build()
+            .map_err(|e| format!("Invalid pattern '{}': {}", self.find, e))
+    }
+}
+
+/// Replacement rules compiled once so they can be applied to many spans of text.
+pub struct CompiledReplacements {
+    rules: Vec<(Regex, ReplacementRule)>,
+}
+
+impl CompiledReplacements {
+    /// Compiles the enabled rules, skipping invalid ones with a warning.
+    pub fn new(rules: &[ReplacementRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| match rule.compile() {
+                Ok(regex) => Some((regex, rule.clone())),
+                Err(e) => {
+                    warn!("Skipping replacement rule: {}", e);
+                    None
+                }
+            })
+            .collect();
+        CompiledReplacements { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies the rules in order, each one to the output of the previous.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for (regex, rule) in &self.rules {
+            result = regex
+                .replace_all(&result, |caps: &Captures| {
+                    let mut replacement = String::new();
+                    if rule.is_regex {
+                        caps.expand(&rule.replace, &mut replacement);
+                    } else {
+                        replacement.push_str(&rule.replace);
+                    }
+                    if rule.case == ReplacementCase::Preserve {
+                        preserve_case_pattern(&caps[0], &replacement)
+                    } else {
+                        replacement
+                    }
+                })
+                .into_owned();
+        }
+        result
+    }
+}
+
+/// Applies replacement rules to `text`. Invalid rules are skipped.
+pub fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> String {
+    CompiledReplacements::new(rules).apply(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(find: &str, replace: &str) -> ReplacementRule {
+        ReplacementRule {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            is_regex: false,
+            case: ReplacementCase::Insensitive,
+            whole_word: true,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn literal_rules_respect_word_boundaries() {
+        let rules = vec![rule("asap", "as soon as possible")];
+        assert_eq!(
+            apply_replacement_rules("Reply ASAP, not asaply", &rules),
+            "Reply as soon as possible, not asaply"
+        );
+
+        let rules = vec![ReplacementRule {
+            whole_word: false,
+            ..rule("asap", "soon")
+        }];
+        assert_eq!(apply_replacement_rules("asaply", &rules), "soonly");
+    }
+
+    #[test]
+    fn literal_rules_do_not_expand_captures() {
+        let rules = vec![rule("price", "$1 dollars"), rule("c++", "C++")];
+        assert_eq!(
+            apply_replacement_rules("the price in c++", &rules),
+            "the $1 dollars in C++"
+        );
+    }
+
+    #[test]
+    fn case_options() {
+        let sensitive = vec![ReplacementRule {
+            case: ReplacementCase::Sensitive,
+            ..rule("Handy", "HANDY")
+        }];
+        assert_eq!(
+            apply_replacement_rules("Handy handy", &sensitive),
+            "HANDY handy"
+        );
+
+        let preserve = vec![ReplacementRule {
+            case: ReplacementCase::Preserve,
+            ..rule("github", "gitlab")
+        }];
+        assert_eq!(
+            apply_replacement_rules("github Github GITHUB", &preserve),
+            "gitlab Gitlab GITLAB"
+        );
+    }
+
+    #[test]
+    fn regex_rules_run_in_order() {
+        let rules = vec![
+            ReplacementRule {
+                is_regex: true,
+                ..rule(r"(\d{3})-(\d{4})", "$1-XXXX")
+            },
+            rule("XXXX", "****"),
+        ];
+        assert_eq!(
+            apply_replacement_rules("call 555-1234", &rules),
+            "call 555-****"
+        );
+    }
+
+    #[test]
+    fn invalid_and_disabled_rules_are_skipped() {
+        let rules = vec![
+            ReplacementRule {
+                is_regex: true,
+                ..rule("(unclosed", "x")
+            },
+            ReplacementRule {
+                enabled: false,
+                ..rule("hello", "bye")
+            },
+        ];
+        assert!(rules[0].compile().is_err());
+        assert_eq!(apply_replacement_rules("hello", &rules), "hello");
+    }
+}
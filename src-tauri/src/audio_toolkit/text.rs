@@ -155,8 +155,48 @@ pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -
     result.join(" ")
 }
 
+/// Matches a whole utterance against a list of spoken phrases
+///
+/// Uses the same scoring as custom words, on the utterance and phrases with
+/// punctuation, case and spaces removed, so "Select all." matches "select all".
+///
+/// # Arguments
+/// * `text` - The transcribed utterance
+/// * `phrases` - Phrases to match against
+/// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
+///
+/// # Returns
+/// The index of the best matching phrase, if any
+pub fn match_phrase(text: &str, phrases: &[String], threshold: f64) -> Option<usize> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let candidate = build_ngram(&words);
+
+    phrases
+        .iter()
+        .enumerate()
+        .filter_map(|(i, phrase)| {
+            let phrase_words: Vec<&str> = phrase.split_whitespace().collect();
+            let phrase_nospace = [build_ngram(&phrase_words)];
+            find_best_match(
+                &candidate,
+                std::slice::from_ref(phrase),
+                &phrase_nospace,
+                threshold,
+            )
+            .map(|(_, score)| (i, score))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+/// Whether the utterance is exactly `phrase`, ignoring case, punctuation and spaces
+pub fn phrase_matches_exactly(text: &str, phrase: &str) -> bool {
+    let spoken = build_ngram(&text.split_whitespace().collect::<Vec<_>>());
+    !spoken.is_empty() && spoken == build_ngram(&phrase.split_whitespace().collect::<Vec<_>>())
+}
+
 /// Preserves the case pattern of the original word when applying a replacement
-fn preserve_case_pattern(original: &str, replacement: &str) -> String {
+pub(crate) fn preserve_case_pattern(original: &str, replacement: &str) -> String {
     if original.chars().all(|c| c.is_uppercase()) {
         replacement.to_uppercase()
     } else if original.chars().next().map_or(false, |c| c.is_uppercase()) {
@@ -194,12 +234,34 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
-/// Filler words to remove from transcriptions
+/// Filler words to remove from English transcriptions
 const FILLER_WORDS: &[&str] = &[
     "uh", "um", "uhm", "umm", "uhh", "uhhh", "ah", "eh", "hmm", "hm", "mmm", "mm", "mh", "ha",
     "ehh",
 ];
 
+// Other languages leave out sounds that are real words there, e.g. "ha" and "eh"
+// in Italian and Spanish
+const FILLER_WORDS_DE: &[&str] = &["äh", "ähm", "öh", "öhm", "ehm", "hmm", "hm", "mmm", "mm"];
+const FILLER_WORDS_ES: &[&str] = &["em", "ehm", "mmm", "mm", "hmm", "hm"];
+const FILLER_WORDS_FR: &[&str] = &["euh", "heu", "hum", "hmm", "hm", "mmm", "mm"];
+const FILLER_WORDS_IT: &[&str] = &["ehm", "uhm", "ehh", "mmm", "mm", "hmm", "hm"];
+const FILLER_WORDS_PT: &[&str] = &["hã", "ahn", "hum", "hmm", "hm", "mmm", "mm"];
+
+/// The built-in filler words for a language code such as `en`, `pt-BR` or `auto`.
+/// Unknown languages, including `auto`, use the English list.
+pub fn builtin_filler_words(language: &str) -> &'static [&'static str] {
+    let base = language.split(['-', '_']).next().unwrap_or(language);
+    match base.to_lowercase().as_str() {
+        "de" => FILLER_WORDS_DE,
+        "es" => FILLER_WORDS_ES,
+        "fr" => FILLER_WORDS_FR,
+        "it" => FILLER_WORDS_IT,
+        "pt" => FILLER_WORDS_PT,
+        _ => FILLER_WORDS,
+    }
+}
+
 static MULTI_SPACE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s{2,}").unwrap());
 
 /// Collapses repeated 1-2 letter words (3+ repetitions) to a single instance.
@@ -242,23 +304,61 @@ fn collapse_stutters(text: &str) -> String {
     result.join(" ")
 }
 
-/// Pre-compiled filler word patterns (built lazily)
-static FILLER_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
-    FILLER_WORDS
-        .iter()
-        .map(|word| {
-            // Match filler word with word boundaries, optionally followed by comma or period
-            Regex::new(&format!(r"(?i)\b{}\b[,.]?", regex::escape(word))).unwrap()
-        })
-        .collect()
-});
+/// Filler word and stutter filtering, compiled once for a given word list.
+pub struct TranscriptionFilter {
+    filler_pattern: Option<Regex>,
+    collapse_stutters: bool,
+}
 
-/// Filters transcription output by removing filler words and stutter artifacts.
-///
-/// This function cleans up raw transcription text by:
-/// 1. Removing filler words (uh, um, hmm, etc.)
-/// 2. Collapsing repeated 1-2 letter stutters (e.g., "wh wh wh" -> "wh")
-/// 3. Cleaning up excess whitespace
+impl TranscriptionFilter {
+    pub fn new<S: AsRef<str>>(filler_words: &[S], collapse_stutters: bool) -> Self {
+        let alternatives: Vec<String> = filler_words
+            .iter()
+            .map(|word| word.as_ref().trim())
+            .filter(|word| !word.is_empty())
+            .map(regex::escape)
+            .collect();
+
+        // Match filler words with word boundaries, optionally followed by comma or period
+        let filler_pattern = (!alternatives.is_empty())
+            .then(|| Regex::new(&format!(r"(?i)\b(?:{})\b[,.]?", alternatives.join("|"))).unwrap());
+
+        TranscriptionFilter {
+            filler_pattern,
+            collapse_stutters,
+        }
+    }
+
+    /// Filters transcription output by removing filler words and stutter artifacts.
+    ///
+    /// This cleans up raw transcription text by:
+    /// 1. Removing filler words (uh, um, hmm, etc.)
+    /// 2. Collapsing repeated 1-2 letter stutters (e.g., "wh wh wh" -> "wh"), if enabled
+    /// 3. Cleaning up excess whitespace
+    pub fn apply(&self, text: &str) -> String {
+        let mut filtered = match &self.filler_pattern {
+            Some(pattern) => pattern.replace_all(text, "").to_string(),
+            None => text.to_string(),
+        };
+
+        // Collapse repeated 1-2 letter words (stutter artifacts like "wh wh wh wh")
+        if self.collapse_stutters {
+            filtered = collapse_stutters(&filtered);
+        }
+
+        // Clean up multiple spaces to single space
+        filtered = MULTI_SPACE_PATTERN.replace_all(&filtered, " ").to_string();
+
+        // Trim leading/trailing whitespace
+        filtered.trim().to_string()
+    }
+}
+
+/// Pre-compiled English filter (built lazily)
+static DEFAULT_FILTER: Lazy<TranscriptionFilter> =
+    Lazy::new(|| TranscriptionFilter::new(FILLER_WORDS, true));
+
+/// Filters transcription output with the English filler words and stutter collapsing.
 ///
 /// # Arguments
 /// * `text` - The raw transcription text to filter
@@ -266,21 +366,7 @@ static FILLER_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
 /// # Returns
 /// The filtered text with filler words and stutters removed
 pub fn filter_transcription_output(text: &str) -> String {
-    let mut filtered = text.to_string();
-
-    // Remove filler words
-    for pattern in FILLER_PATTERNS.iter() {
-        filtered = pattern.replace_all(&filtered, "").to_string();
-    }
-
-    // Collapse repeated 1-2 letter words (stutter artifacts like "wh wh wh wh")
-    filtered = collapse_stutters(&filtered);
-
-    // Clean up multiple spaces to single space
-    filtered = MULTI_SPACE_PATTERN.replace_all(&filtered, " ").to_string();
-
-    // Trim leading/trailing whitespace
-    filtered.trim().to_string()
+    DEFAULT_FILTER.apply(text)
 }
 
 #[cfg(test)]
@@ -444,6 +530,47 @@ mod tests {
         assert!(result.contains("MacBook"));
     }
 
+    #[test]
+    fn test_filler_words_per_language() {
+        let italian = TranscriptionFilter::new(builtin_filler_words("it"), true);
+        assert_eq!(
+            italian.apply("ehm lui ha detto eh sì"),
+            "lui ha detto eh sì"
+        );
+        assert_eq!(builtin_filler_words("auto"), FILLER_WORDS);
+        assert_eq!(builtin_filler_words("pt-BR"), FILLER_WORDS_PT);
+    }
+
+    #[test]
+    fn test_filter_without_stutter_collapse() {
+        let filter = TranscriptionFilter::new(&["um"], false);
+        assert_eq!(filter.apply("um no no no way"), "no no no way");
+
+        let no_fillers = TranscriptionFilter::new::<&str>(&[], true);
+        assert_eq!(no_fillers.apply("uh I I I see"), "uh I see");
+    }
+
+    #[test]
+    fn test_match_phrase() {
+        let phrases = vec![
+            "new paragraph".to_string(),
+            "select all".to_string(),
+            "send message".to_string(),
+        ];
+        assert_eq!(match_phrase("Select all.", &phrases, 0.2), Some(1));
+        assert_eq!(match_phrase("send a message", &phrases, 0.2), Some(2));
+        assert_eq!(match_phrase("new paragraphs", &phrases, 0.2), Some(0));
+        assert_eq!(
+            match_phrase("please select all of the text", &phrases, 0.2),
+            None
+        );
+        assert_eq!(match_phrase("", &phrases, 0.2), None);
+
+        assert!(phrase_matches_exactly("Select all.", "select all"));
+        assert!(!phrase_matches_exactly("select al", "select all"));
+        assert!(!phrase_matches_exactly("", ""));
+    }
+
     #[test]
     fn test_apply_custom_words_trailing_number_not_doubled() {
         // Verify that trailing non-alpha chars (like numbers) aren't double-counted
@@ -0,0 +1,266 @@
+//! Headless command-line mode.
+//!
+//! `handy transcribe <file>` runs an audio file through the same engines and text pipeline
+//! used by the transcribe shortcut, without opening any windows or registering shortcuts.
+
+use crate::actions::{process_transcription, ProcessedTranscription};
+use crate::audio_toolkit::{load_audio_file, DetectedLanguage};
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::{TimedText, TranscriptionManager};
+use crate::settings::get_settings;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+const USAGE: &str = "\
+Usage: handy transcribe <file> [options]
+
+Transcribe an audio file (WAV, FLAC, ...) with the models and settings configured in Handy.
+
+Options:
+  --model <id>       Use this model instead of the selected one
+  --language <code>  Override the selected language (e.g. en, de, auto)
+  --post-process     Run LLM post-processing with the selected provider and prompt
+  --json             Print a JSON object instead of plain text
+  -h, --help         Show this help message";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscribeArgs {
+    pub file: PathBuf,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub post_process: bool,
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Transcribe(TranscribeArgs),
+    Help,
+}
+
+#[derive(Serialize)]
+struct TranscribeOutput {
+    file: String,
+    model: String,
+    text: String,
+    raw_text: String,
+    post_processed_text: Option<String>,
+    post_process_prompt: Option<String>,
+    post_process_provider: Option<String>,
+    segments: Vec<TimedText>,
+    words: Option<Vec<TimedText>>,
+    /// Detected language, when transcribing with language "auto"
+    language: Option<DetectedLanguage>,
+    audio_duration_secs: f64,
+    elapsed_ms: u128,
+}
+
+/// Parse the process arguments. Returns `None` when Handy should start normally.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Option<Result<CliCommand>> {
+    let mut args = args.into_iter().skip(1);
+    if args.next().as_deref() != Some("transcribe") {
+        return None;
+    }
+    Some(parse_transcribe_args(args))
+}
+
+fn parse_transcribe_args(mut args: impl Iterator<Item = String>) -> Result<CliCommand> {
+    let mut file = None;
+    let mut model = None;
+    let mut language = None;
+    let mut post_process = false;
+    let mut json = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "--json" => json = true,
+            "--post-process" => post_process = true,
+            "--model" => {
+                model = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow!("--model requires a value"))?,
+                )
+            }
+            "--language" => {
+                language = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow!("--language requires a value"))?,
+                )
+            }
+            other if other.starts_with('-') => return Err(anyhow!("Unknown option: {}", other)),
+            other => {
+                if file.is_some() {
+                    return Err(anyhow!("Only one input file can be transcribed at a time"));
+                }
+                file = Some(PathBuf::from(other));
+            }
+        }
+    }
+
+    let file = file.ok_or_else(|| anyhow!("Missing input file"))?;
+    Ok(CliCommand::Transcribe(TranscribeArgs {
+        file,
+        model,
+        language,
+        post_process,
+        json,
+    }))
+}
+
+/// Release builds on Windows use the GUI subsystem and start without a console, so
+/// attach to the one of the shell that launched us to make stdout and stderr visible.
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // Fails when there is no parent console (e.g. started from Explorer) or when the
+    // process already has one, as debug builds do. Output is then simply not shown.
+    let _ = unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
+#[cfg(not(windows))]
+pub fn attach_parent_console() {}
+
+pub fn print_usage() {
+    eprintln!("{}", USAGE);
+}
+
+/// Load the requested model, transcribe the file and print the result to stdout.
+pub fn run_transcribe(app_handle: &AppHandle, args: &TranscribeArgs) -> Result<()> {
+    let model_manager = Arc::new(ModelManager::new(app_handle)?);
+    let transcription_manager = Arc::new(TranscriptionManager::new(
+        app_handle,
+        model_manager.clone(),
+    )?);
+    app_handle.manage(model_manager.clone());
+    app_handle.manage(transcription_manager.clone());
+
+    // Language overrides only apply to this run, they are never persisted
+    let mut settings = get_settings(app_handle);
+    if let Some(language) = &args.language {
+        settings.selected_language = language.clone();
+    }
+
+    let model_id = args
+        .model
+        .clone()
+        .unwrap_or_else(|| settings.selected_model.clone());
+    if model_id.is_empty() {
+        return Err(anyhow!(
+            "No model selected. Pass --model <id> or select a model in Handy first"
+        ));
+    }
+    let model_info = model_manager
+        .get_model_info(&model_id)
+        .ok_or_else(|| anyhow!("Unknown model: {}", model_id))?;
+    if !model_info.is_downloaded {
+        return Err(anyhow!(
+            "Model '{}' is not downloaded. Download it from Handy first",
+            model_id
+        ));
+    }
+
+    let samples = load_audio_file(&args.file)
+        .map_err(|e| anyhow!("Failed to read {}: {}", args.file.display(), e))?;
+    let audio_duration_secs =
+        samples.len() as f64 / crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE as f64;
+
+    let start = Instant::now();
+    transcription_manager.load_model(&model_id)?;
+    let transcription = transcription_manager.transcribe_with_settings(samples, &settings)?;
+
+    let ProcessedTranscription {
+        final_text,
+        post_processed_text,
+        post_process_prompt,
+        post_process_provider,
+    } = tauri::async_runtime::block_on(process_transcription(
+        &settings,
+        &transcription.text,
+        transcription.language_or(&settings.selected_language),
+        args.post_process,
+        None,
+    ));
+
+    if args.json {
+        let output = TranscribeOutput {
+            file: args.file.display().to_string(),
+            model: model_id,
+            text: final_text,
+            raw_text: transcription.raw_text,
+            post_processed_text,
+            post_process_prompt,
+            post_process_provider,
+            segments: transcription.segments,
+            words: transcription.words,
+            language: transcription.language,
+            audio_duration_secs,
+            elapsed_ms: start.elapsed().as_millis(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", final_text);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_ignores_normal_startup() {
+        assert!(parse_args(args(&["handy"])).is_none());
+        assert!(parse_args(args(&["handy", "--start-hidden"])).is_none());
+    }
+
+    #[test]
+    fn test_parse_transcribe_args() {
+        let cmd = parse_args(args(&[
+            "handy",
+            "transcribe",
+            "meeting.flac",
+            "--model",
+            "parakeet-tdt-0.6b-v3",
+            "--json",
+        ]))
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            cmd,
+            CliCommand::Transcribe(TranscribeArgs {
+                file: PathBuf::from("meeting.flac"),
+                model: Some("parakeet-tdt-0.6b-v3".to_string()),
+                language: None,
+                post_process: false,
+                json: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_transcribe_args_errors() {
+        assert!(parse_args(args(&["handy", "transcribe"])).unwrap().is_err());
+        assert!(
+            parse_args(args(&["handy", "transcribe", "a.wav", "--model"]))
+                .unwrap()
+                .is_err()
+        );
+        assert!(
+            parse_args(args(&["handy", "transcribe", "a.wav", "--bogus"]))
+                .unwrap()
+                .is_err()
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use crate::input::{self, EnigoState};
-use crate::settings::{get_settings, ClipboardHandling, PasteMethod};
+use crate::settings::{AppSettings, ClipboardHandling, PasteMethod};
 use enigo::Enigo;
-use log::info;
+use log::{error, info};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -446,8 +448,8 @@ fn paste_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
     input::paste_text_direct(enigo, text)
 }
 
-pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
-    let settings = get_settings(&app_handle);
+/// Deliver `text` to the focused application using the paste options in `settings`.
+pub fn paste(text: String, app_handle: AppHandle, settings: &AppSettings) -> Result<(), String> {
     let paste_method = settings.paste_method;
     let paste_delay_ms = settings.paste_delay_ms;
 
@@ -477,7 +479,7 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
         PasteMethod::None => {
             info!("PasteMethod::None selected - skipping paste action");
         }
-        PasteMethod::Direct => {
+        PasteMethod::Direct | PasteMethod::DirectStreaming => {
             paste_direct(&mut enigo, &text)?;
         }
         PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
@@ -501,3 +503,138 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Bumped on cancellation; a [`StreamingPaste`] stops typing once it no longer matches.
+static PASTE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Stop any [`StreamingPaste`] that is currently typing.
+pub fn cancel_streaming_paste() {
+    PASTE_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Types text into the focused application piece by piece while it is being generated,
+/// using the same direct-typing backends as [`PasteMethod::Direct`].
+pub struct StreamingPaste {
+    app_handle: AppHandle,
+    generation: u64,
+    pending: Mutex<String>,
+    typed: AtomicBool,
+    /// Everything handed to the typing backend so far
+    typed_text: Mutex<String>,
+}
+
+impl StreamingPaste {
+    pub fn begin(app_handle: &AppHandle) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            generation: PASTE_GENERATION.load(Ordering::SeqCst),
+            pending: Mutex::new(String::new()),
+            typed: AtomicBool::new(false),
+            typed_text: Mutex::new(String::new()),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        PASTE_GENERATION.load(Ordering::SeqCst) != self.generation
+    }
+
+    /// Whether any text has been handed to the typing backend yet.
+    pub fn has_typed(&self) -> bool {
+        self.typed.load(Ordering::SeqCst)
+    }
+
+    /// Queue a piece of generated text. Complete words are typed right away, the
+    /// rest waits for more text or [`finish`](Self::finish). Returns `false` once cancelled.
+    pub fn push(&self, text: &str) -> bool {
+        if self.is_cancelled() {
+            return false;
+        }
+        let ready = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.push_str(text);
+            take_complete_words(&mut pending)
+        };
+        if let Some(ready) = ready {
+            self.type_text(ready);
+        }
+        true
+    }
+
+    /// Type whatever is still pending, plus `suffix` (e.g. the trailing space).
+    pub fn finish(&self, suffix: &str) {
+        let mut rest = std::mem::take(&mut *self.pending.lock().unwrap());
+        if self.has_typed() || !rest.is_empty() {
+            rest.push_str(suffix);
+        }
+        if !rest.is_empty() && !self.is_cancelled() {
+            self.type_text(rest);
+        }
+    }
+
+    /// Stop after the stream broke off: the pending partial word is dropped and the
+    /// text typed so far is returned, which is what ends up in the focused app.
+    pub fn abort(&self) -> String {
+        self.pending.lock().unwrap().clear();
+        self.typed_text.lock().unwrap().clone()
+    }
+
+    fn type_text(&self, text: String) {
+        self.typed.store(true, Ordering::SeqCst);
+        self.typed_text.lock().unwrap().push_str(&text);
+
+        // Typing must happen on the main thread; closures run in the order they are queued
+        let app_handle = self.app_handle.clone();
+        let generation = self.generation;
+        let result = self.app_handle.run_on_main_thread(move || {
+            if PASTE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let Some(enigo_state) = app_handle.try_state::<EnigoState>() else {
+                error!("Enigo state not initialized");
+                return;
+            };
+            let Ok(mut enigo) = enigo_state.0.lock() else {
+                error!("Failed to lock Enigo");
+                return;
+            };
+            if let Err(e) = paste_direct(&mut enigo, &text) {
+                error!("Failed to type streamed text: {}", e);
+            }
+        });
+        if let Err(e) = result {
+            error!("Failed to run streamed typing on main thread: {:?}", e);
+        }
+    }
+}
+
+/// Split off everything up to and including the last whitespace in `pending`, so
+/// words are never typed half-way (each backend call has a fixed startup cost).
+fn take_complete_words(pending: &mut String) -> Option<String> {
+    let end = pending
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .last()
+        .map(|(i, c)| i + c.len_utf8())?;
+    let rest = pending.split_off(end);
+    Some(std::mem::replace(pending, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_complete_words_keeps_partial_word() {
+        let mut pending = "Hello wor".to_string();
+        assert_eq!(take_complete_words(&mut pending).as_deref(), Some("Hello "));
+        assert_eq!(pending, "wor");
+
+        assert_eq!(take_complete_words(&mut pending), None);
+        pending.push_str("ld.\nNext");
+        assert_eq!(
+            take_complete_words(&mut pending).as_deref(),
+            Some("world.\n")
+        );
+        assert_eq!(pending, "Next");
+    }
+}
@@ -1,4 +1,12 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::actions::{process_transcription, ProcessedTranscription};
+use crate::audio_toolkit::load_audio_file;
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::history::{
+    HistoryEntry, HistoryManager, HistoryPage, HistoryPageQuery, HistoryRevision,
+    HistorySearchFilters, HistorySearchResult,
+};
+use crate::managers::history_export::HistoryExportRequest;
+use crate::managers::transcription::{Transcription, TranscriptionManager};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -14,6 +22,33 @@ pub async fn get_history_entries(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_page(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: HistoryPageQuery,
+) -> Result<HistoryPage, String> {
+    history_manager
+        .get_history_page(&query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_history(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    filters: Option<HistorySearchFilters>,
+) -> Result<Vec<HistorySearchResult>, String> {
+    history_manager
+        .search_history(&query, &filters.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn toggle_history_entry_saved(
@@ -53,6 +88,127 @@ pub async fn delete_history_entry(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    request: HistoryExportRequest,
+) -> Result<usize, String> {
+    history_manager
+        .export_entries(&request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_revisions(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    history_id: i64,
+) -> Result<Vec<HistoryRevision>, String> {
+    history_manager
+        .get_revisions(history_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-run a history entry through a different model and/or post-processing prompt.
+/// The result is stored as a revision of the entry; the original is never overwritten.
+#[tauri::command]
+#[specta::specta]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    audio_manager: State<'_, Arc<AudioRecordingManager>>,
+    id: i64,
+    model_id: Option<String>,
+    prompt_id: Option<String>,
+) -> Result<HistoryRevision, String> {
+    if model_id.is_none() && prompt_id.is_none() {
+        return Err("Select a model or a prompt to re-transcribe with".to_string());
+    }
+    if audio_manager.is_recording() {
+        return Err("Cannot re-transcribe while recording".to_string());
+    }
+
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+
+    let mut settings = crate::settings::get_settings(&app);
+    if let Some(prompt_id) = &prompt_id {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Unknown prompt: {}", prompt_id));
+        }
+        settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+        settings.post_process_language_prompts.clear();
+    }
+
+    let transcription = match &model_id {
+        Some(model_id) => {
+            let path = history_manager.get_audio_file_path(&entry.file_name);
+            let samples = load_audio_file(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+            let tm = transcription_manager.inner().clone();
+            let model_id = model_id.clone();
+            let task_settings = settings.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                tm.transcribe_with_model(&model_id, samples, &task_settings)
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?
+        }
+        // Only re-run post-processing on the original transcript
+        None => Transcription {
+            text: entry.transcription_text.clone(),
+            segments: entry.segments.clone(),
+            words: entry.words.clone(),
+            language: entry.language.clone(),
+            raw_text: entry.transcription_text.clone(),
+        },
+    };
+
+    let ProcessedTranscription {
+        post_processed_text,
+        post_process_prompt,
+        post_process_provider,
+        ..
+    } = process_transcription(
+        &settings,
+        &transcription.text,
+        transcription.language_or(&settings.selected_language),
+        prompt_id.is_some(),
+        None,
+    )
+    .await;
+
+    if prompt_id.is_some() && post_process_provider.is_none() {
+        return Err("Post-processing failed, check the provider settings".to_string());
+    }
+
+    history_manager
+        .add_revision(
+            id,
+            model_id,
+            transcription,
+            post_processed_text,
+            post_process_prompt,
+            post_process_provider,
+        )
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn update_history_limit(
@@ -1,6 +1,10 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::download_queue::DownloadQueueState;
+use crate::managers::model::{ModelImportRequest, ModelInfo, ModelManager};
+use crate::managers::model_catalog::infer_engine_type;
+use crate::managers::model_checksum::ModelVerification;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -21,6 +25,8 @@ pub async fn get_model_info(
     Ok(model_manager.get_model_info(&model_id))
 }
 
+/// Queue a model download and wait for it to end. Progress is reported as events;
+/// a paused or cancelled download returns early without an error.
 #[tauri::command]
 #[specta::specta]
 pub async fn download_model(
@@ -33,6 +39,83 @@ pub async fn download_model(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn pause_download(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    model_manager
+        .pause_download(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_download(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    model_manager
+        .resume_download(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn prioritize_download(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    model_manager
+        .prioritize_download(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_queue(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<DownloadQueueState, String> {
+    Ok(model_manager.get_download_queue())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_max_concurrent_downloads(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    count: u32,
+) -> Result<(), String> {
+    if count == 0 {
+        return Err("At least one download has to be allowed".to_string());
+    }
+    let mut settings = get_settings(&app_handle);
+    settings.max_concurrent_downloads = count;
+    write_settings(&app_handle, settings);
+
+    // A higher limit frees slots for queued downloads right away
+    model_manager.start_queued_downloads();
+    Ok(())
+}
+
+/// Limit the combined speed of model downloads. `None` or 0 removes the limit.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_download_speed_limit(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    bytes_per_second: Option<u64>,
+) -> Result<(), String> {
+    let bytes_per_second = bytes_per_second.filter(|limit| *limit > 0);
+    let mut settings = get_settings(&app_handle);
+    settings.download_speed_limit = bytes_per_second;
+    write_settings(&app_handle, settings);
+
+    model_manager.set_download_speed_limit(bytes_per_second);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_model(
@@ -132,6 +215,181 @@ pub async fn has_any_models_or_downloads(
     Ok(models.iter().any(|m| m.is_downloaded))
 }
 
+/// Import a local model file or directory. The engine is inferred from the file layout
+/// unless given, and the model must load with it before it is added.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    request: ModelImportRequest,
+) -> Result<ModelInfo, String> {
+    let source = PathBuf::from(&request.path);
+    let engine_type = request
+        .engine_type
+        .clone()
+        .or_else(|| infer_engine_type(&source))
+        .ok_or("Could not tell which engine this model is for, please choose one")?;
+
+    // Loading a large model takes a while, keep it off the async runtime
+    let validate_engine = engine_type.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        TranscriptionManager::validate_model(&validate_engine, &source)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    model_manager
+        .import_model(&request, engine_type)
+        .map_err(|e| e.to_string())
+}
+
+/// Check a downloaded model against its SHA-256 checksum. A corrupted model is
+/// downloaded again, reloading it if it was the active one.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_id: String,
+) -> Result<ModelVerification, String> {
+    // Hashing a large model takes a while, keep it off the async runtime
+    let manager = model_manager.inner().clone();
+    let id = model_id.clone();
+    let matches = tauri::async_runtime::spawn_blocking(move || manager.check_model(&id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    match matches {
+        None => Ok(ModelVerification::Unverifiable),
+        Some(true) => Ok(ModelVerification::Verified),
+        Some(false) => {
+            let was_loaded =
+                transcription_manager.get_current_model().as_deref() == Some(model_id.as_str());
+            if was_loaded {
+                transcription_manager
+                    .unload_model()
+                    .map_err(|e| format!("Failed to unload model: {}", e))?;
+            }
+
+            model_manager
+                .redownload_model(&model_id)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if was_loaded {
+                transcription_manager
+                    .load_model(&model_id)
+                    .map_err(|e| e.to_string())?;
+            }
+            Ok(ModelVerification::Redownloaded)
+        }
+    }
+}
+
+/// Re-read the model manifests and fetch the remote catalog, if one is configured.
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_model_catalog(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelInfo>, String> {
+    model_manager
+        .refresh_catalog()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(model_manager.get_available_models())
+}
+
+/// Trims an optional URL setting, treating an empty one as unset.
+fn http_url_setting(url: Option<String>, what: &str) -> Result<Option<String>, String> {
+    let url = url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    if let Some(url) = &url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("{} must start with http:// or https://", what));
+        }
+    }
+    Ok(url)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_model_catalog_url(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    url: Option<String>,
+) -> Result<Vec<ModelInfo>, String> {
+    let url = http_url_setting(url, "The catalog URL")?;
+    // The catalog decides what gets downloaded, so it must not be tampered with on the way
+    if url.as_ref().is_some_and(|url| !url.starts_with("https://")) {
+        return Err("The catalog URL must start with https://".to_string());
+    }
+
+    let mut settings = get_settings(&app_handle);
+    settings.model_catalog_url = url;
+    write_settings(&app_handle, settings);
+
+    // The cached catalog belongs to the previous URL
+    model_manager
+        .clear_remote_catalog()
+        .map_err(|e| e.to_string())?;
+    model_manager
+        .refresh_catalog()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(model_manager.get_available_models())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_model_mirror_url(
+    app_handle: AppHandle,
+    url: Option<String>,
+) -> Result<(), String> {
+    let url = http_url_setting(url, "The mirror URL")?;
+    let mut settings = get_settings(&app_handle);
+    settings.model_mirror_url = url;
+    write_settings(&app_handle, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_model_download_proxy(
+    app_handle: AppHandle,
+    proxy: Option<String>,
+) -> Result<(), String> {
+    let proxy = http_url_setting(proxy, "The proxy URL")?;
+    if let Some(proxy) = &proxy {
+        reqwest::Proxy::all(proxy).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    }
+    let mut settings = get_settings(&app_handle);
+    settings.model_download_proxy = proxy;
+    write_settings(&app_handle, settings);
+    Ok(())
+}
+
+/// Install a model from a `.bin` or `.tar.gz` that was downloaded elsewhere. The model
+/// is matched by file name unless `model_id` is given.
+#[tauri::command]
+#[specta::specta]
+pub async fn install_model_bundle(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+    model_id: Option<String>,
+) -> Result<ModelInfo, String> {
+    // Copying, hashing and extracting a large model takes a while
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        manager.install_model_bundle(model_id.as_deref(), &PathBuf::from(path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn cancel_download(
@@ -0,0 +1,514 @@
+//! Local control socket for driving Handy from scripts, editors and hardware controllers.
+//!
+//! Handy listens on a Unix domain socket (`handy.sock` in `$XDG_RUNTIME_DIR` or the app
+//! data directory) or on the `\\.\pipe\handy-control` named pipe on Windows. Clients send
+//! one JSON request per line and receive one JSON response per line:
+//!
+//! ```text
+//! {"cmd":"start","binding":"transcribe"}              -> {"ok":true}
+//! {"cmd":"stop","binding":"transcribe","wait":true}   -> {"ok":true,"text":"hello world"}
+//! {"cmd":"toggle"}                                    -> {"ok":true,"recording":true}
+//! {"cmd":"cancel"}                                    -> {"ok":true}
+//! {"cmd":"status"}                                    -> {"ok":true,"recording":false,...}
+//! {"cmd":"subscribe"}                                 -> {"ok":true} followed by one
+//!                                                        {"event":"transcription",...} line
+//!                                                        per completed transcription
+//! ```
+//!
+//! `binding` defaults to `transcribe` and may name any binding in `ACTION_MAP`.
+
+use crate::actions::{action_for, is_transcribe_binding, TranscriptionCompleteEvent};
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use crate::utils::cancel_current_operation;
+use crate::ManagedToggleState;
+use futures_util::future::{select, Either};
+use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Listener, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::sync::mpsc;
+
+const SOCKET_TRIGGER: &str = "control-socket";
+const DEFAULT_BINDING: &str = "transcribe";
+const TRANSCRIPT_WAIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\handy-control";
+
+static SERVER: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum ControlRequest {
+    Start {
+        binding: Option<String>,
+    },
+    Stop {
+        binding: Option<String>,
+        #[serde(default)]
+        wait: bool,
+    },
+    Toggle {
+        binding: Option<String>,
+        #[serde(default)]
+        wait: bool,
+    },
+    Cancel,
+    Status,
+    Subscribe,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ControlResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recording: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+impl ControlResponse {
+    fn ok() -> Self {
+        Self {
+            ok: true,
+            ..Default::default()
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(message.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TranscriptionLine<'a> {
+    event: &'static str,
+    #[serde(flatten)]
+    payload: &'a TranscriptionCompleteEvent,
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+/// Start the control socket server if it is not already running.
+pub fn start_server(app: &AppHandle) {
+    let mut server = SERVER.lock().unwrap();
+    if server.is_some() {
+        return;
+    }
+
+    let app = app.clone();
+    *server = Some(tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(app).await {
+            error!("Control socket stopped: {}", e);
+        }
+    }));
+}
+
+/// Stop the control socket server and remove the socket file.
+pub fn stop_server(app: &AppHandle) {
+    if let Some(handle) = SERVER.lock().unwrap().take() {
+        handle.abort();
+        info!("Control socket stopped");
+    }
+
+    #[cfg(unix)]
+    if let Ok(path) = socket_path(app) {
+        let _ = std::fs::remove_file(path);
+    }
+    #[cfg(not(unix))]
+    let _ = app;
+}
+
+#[cfg(unix)]
+pub fn socket_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        return Ok(std::path::PathBuf::from(runtime_dir).join("handy.sock"));
+    }
+
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("handy.sock"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+#[cfg(unix)]
+async fn serve(app: AppHandle) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::UnixListener;
+
+    let path = socket_path(&app).map_err(anyhow::Error::msg)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A stale socket from a previous run would make bind() fail
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    // Only the current user may drive dictation
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    info!("Control socket listening on {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tauri::async_runtime::spawn(handle_connection(app.clone(), stream));
+    }
+}
+
+#[cfg(windows)]
+async fn serve(app: AppHandle) -> anyhow::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(PIPE_NAME)?;
+    info!("Control pipe listening on {}", PIPE_NAME);
+
+    loop {
+        server.connect().await?;
+        let connected = server;
+        // Create the next instance before handing off so clients never see a missing pipe
+        server = ServerOptions::new()
+            .reject_remote_clients(true)
+            .create(PIPE_NAME)?;
+        tauri::async_runtime::spawn(handle_connection(app.clone(), connected));
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+async fn serve(_app: AppHandle) -> anyhow::Result<()> {
+    Err(anyhow::anyhow!(
+        "Control socket is not supported on this platform"
+    ))
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+async fn handle_connection<S>(app: AppHandle, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                debug!("Control socket read failed: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                let response = ControlResponse::error(format!("Invalid request: {}", e));
+                if write_line(&mut writer, &response).await.is_err() {
+                    break;
+                }
+                continue;
+            }
+        };
+        debug!("Control socket request: {:?}", request);
+
+        if let ControlRequest::Subscribe = request {
+            if write_line(&mut writer, &ControlResponse::ok())
+                .await
+                .is_ok()
+            {
+                forward_transcriptions(&app, &mut lines, &mut writer).await;
+            }
+            break;
+        }
+
+        let response = handle_request(&app, request).await;
+        if write_line(&mut writer, &response).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn write_line<W, T>(writer: &mut W, value: &T) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await
+}
+
+async fn handle_request(app: &AppHandle, request: ControlRequest) -> ControlResponse {
+    match request {
+        ControlRequest::Start { binding } => {
+            let binding = binding.unwrap_or_else(|| DEFAULT_BINDING.to_string());
+            match trigger(app, binding, Some(true)).await {
+                Ok(_) => ControlResponse::ok(),
+                Err(e) => ControlResponse::error(e),
+            }
+        }
+        ControlRequest::Stop { binding, wait } => {
+            let binding = binding.unwrap_or_else(|| DEFAULT_BINDING.to_string());
+            trigger_and_wait(app, binding, Some(false), wait).await
+        }
+        ControlRequest::Toggle { binding, wait } => {
+            let binding = binding.unwrap_or_else(|| DEFAULT_BINDING.to_string());
+            trigger_and_wait(app, binding, None, wait).await
+        }
+        ControlRequest::Cancel => {
+            let app = app.clone();
+            let _ =
+                tauri::async_runtime::spawn_blocking(move || cancel_current_operation(&app)).await;
+            ControlResponse::ok()
+        }
+        ControlRequest::Status => {
+            let rm = app.state::<Arc<AudioRecordingManager>>();
+            let tm = app.state::<Arc<TranscriptionManager>>();
+            let binding = rm.active_binding();
+            ControlResponse {
+                ok: true,
+                recording: Some(binding.is_some()),
+                binding,
+                model: tm.get_current_model(),
+                ..Default::default()
+            }
+        }
+        ControlRequest::Subscribe => unreachable!("subscribe is handled by the connection"),
+    }
+}
+
+/// Trigger a binding and, if it stopped a recording and `wait` is set, block until the
+/// resulting transcription is available.
+async fn trigger_and_wait(
+    app: &AppHandle,
+    binding: String,
+    desired: Option<bool>,
+    wait: bool,
+) -> ControlResponse {
+    // Subscribe before stopping so a fast transcription can't slip past us
+    let mut transcripts = wait.then(|| subscribe_transcriptions(app));
+
+    let started = match trigger(app, binding.clone(), desired).await {
+        Ok(started) => started,
+        Err(e) => {
+            if let Some((id, _)) = transcripts {
+                app.unlisten(id);
+            }
+            return ControlResponse::error(e);
+        }
+    };
+
+    let mut response = ControlResponse {
+        ok: true,
+        recording: Some(started),
+        ..Default::default()
+    };
+
+    if let Some((id, rx)) = transcripts.as_mut() {
+        if !started {
+            match wait_for_transcription(rx, &binding).await {
+                Some(event) => match event.error {
+                    Some(error) => response = ControlResponse::error(error),
+                    None => response.text = Some(event.text),
+                },
+                None => response = ControlResponse::error("Timed out waiting for transcription"),
+            }
+        }
+        app.unlisten(*id);
+    }
+
+    response
+}
+
+/// Start (`Some(true)`), stop (`Some(false)`) or toggle (`None`) a binding, keeping the
+/// shared toggle state in sync with keyboard shortcuts. Returns whether the binding is
+/// now active.
+async fn trigger(app: &AppHandle, binding: String, desired: Option<bool>) -> Result<bool, String> {
+    if !is_transcribe_binding(&get_settings(app), &binding) {
+        return Err(format!("Binding '{}' does not transcribe", binding));
+    }
+    let Some(action) = action_for(&binding) else {
+        return Err(format!("Unknown binding '{}'", binding));
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // Determine the transition while holding the lock, but release it before calling
+        // the action since actions may need to acquire it themselves
+        let should_start = {
+            let toggle_state_manager = app.state::<ManagedToggleState>();
+            let mut states = toggle_state_manager
+                .lock()
+                .map_err(|e| format!("Failed to lock toggle state: {}", e))?;
+
+            // Push-to-talk recordings never touch the toggle state
+            let recording_this = app
+                .state::<Arc<AudioRecordingManager>>()
+                .active_binding()
+                .as_deref()
+                == Some(binding.as_str());
+            let is_active = states
+                .active_toggles
+                .entry(binding.clone())
+                .or_insert(false);
+            let currently_active = *is_active || recording_this;
+
+            let should_start = desired.unwrap_or(!currently_active);
+            if should_start == currently_active {
+                return Err(if should_start {
+                    format!("Binding '{}' is already active", binding)
+                } else {
+                    format!("Binding '{}' is not active", binding)
+                });
+            }
+            *is_active = should_start;
+            should_start
+        };
+
+        if should_start {
+            action.start(&app, &binding, SOCKET_TRIGGER);
+        } else {
+            action.stop(&app, &binding, SOCKET_TRIGGER);
+        }
+        Ok(should_start)
+    })
+    .await
+    .map_err(|e| format!("Failed to run action: {}", e))?
+}
+
+fn subscribe_transcriptions(
+    app: &AppHandle,
+) -> (
+    tauri::EventId,
+    mpsc::UnboundedReceiver<TranscriptionCompleteEvent>,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let id = app.listen(
+        "transcription-complete",
+        move |event| match serde_json::from_str::<TranscriptionCompleteEvent>(event.payload()) {
+            Ok(payload) => {
+                let _ = tx.send(payload);
+            }
+            Err(e) => warn!("Failed to parse transcription-complete payload: {}", e),
+        },
+    );
+    (id, rx)
+}
+
+async fn wait_for_transcription(
+    rx: &mut mpsc::UnboundedReceiver<TranscriptionCompleteEvent>,
+    binding: &str,
+) -> Option<TranscriptionCompleteEvent> {
+    tokio::time::timeout(TRANSCRIPT_WAIT_TIMEOUT, async {
+        while let Some(event) = rx.recv().await {
+            if event.binding_id == binding {
+                return Some(event);
+            }
+        }
+        None
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Writes each transcription to a subscribed client until it disconnects. The
+/// connection is read as well, so a client that goes away between transcriptions
+/// releases its event listener right away.
+async fn forward_transcriptions<R, W>(
+    app: &AppHandle,
+    lines: &mut Lines<BufReader<R>>,
+    writer: &mut W,
+) where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let (id, mut rx) = subscribe_transcriptions(app);
+    loop {
+        let next = {
+            let event = pin!(rx.recv());
+            let input = pin!(lines.next_line());
+            match select(event, input).await {
+                Either::Left((event, _)) => event,
+                // Subscribers have nothing more to ask, ignore anything they send
+                Either::Right((Ok(Some(_)), _)) => continue,
+                Either::Right(_) => {
+                    debug!("Control socket subscriber disconnected");
+                    None
+                }
+            }
+        };
+        let Some(event) = next else {
+            break;
+        };
+
+        let line = TranscriptionLine {
+            event: "transcription",
+            payload: &event,
+        };
+        if write_line(writer, &line).await.is_err() {
+            break;
+        }
+    }
+    app.unlisten(id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requests() {
+        let request: ControlRequest =
+            serde_json::from_str(r#"{"cmd":"stop","binding":"transcribe","wait":true}"#).unwrap();
+        assert!(matches!(
+            request,
+            ControlRequest::Stop { binding: Some(ref b), wait: true } if b == "transcribe"
+        ));
+
+        let request: ControlRequest = serde_json::from_str(r#"{"cmd":"toggle"}"#).unwrap();
+        assert!(matches!(
+            request,
+            ControlRequest::Toggle {
+                binding: None,
+                wait: false
+            }
+        ));
+
+        assert!(serde_json::from_str::<ControlRequest>(r#"{"cmd":"explode"}"#).is_err());
+    }
+
+    #[test]
+    fn test_response_omits_empty_fields() {
+        let json = serde_json::to_string(&ControlResponse::ok()).unwrap();
+        assert_eq!(json, r#"{"ok":true}"#);
+
+        let json = serde_json::to_string(&ControlResponse::error("nope")).unwrap();
+        assert_eq!(json, r#"{"ok":false,"error":"nope"}"#);
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::process::Command;
+
+/// The application that has keyboard focus, as far as the platform lets us tell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActiveApp {
+    /// Executable name without extension, e.g. `slack` or `Code`
+    pub process_name: Option<String>,
+    /// X11 WM_CLASS or Win32 window class
+    pub window_class: Option<String>,
+    /// macOS bundle identifier, e.g. `com.tinyspeck.slackmacgap`
+    pub bundle_id: Option<String>,
+}
+
+impl ActiveApp {
+    /// Whether `matcher` names this app. Matching is case-insensitive against the
+    /// process name (with or without `.exe`), window class and bundle id.
+    pub fn matches(&self, matcher: &str) -> bool {
+        let matcher = matcher.trim();
+        if matcher.is_empty() {
+            return false;
+        }
+        let matcher = strip_exe(matcher);
+
+        [&self.process_name, &self.window_class, &self.bundle_id]
+            .into_iter()
+            .flatten()
+            .any(|name| strip_exe(name).eq_ignore_ascii_case(matcher))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.process_name.is_none() && self.window_class.is_none() && self.bundle_id.is_none()
+    }
+}
+
+fn strip_exe(name: &str) -> &str {
+    let len = name.len();
+    match name.get(len.saturating_sub(4)..) {
+        Some(ext) if len > 4 && ext.eq_ignore_ascii_case(".exe") => &name[..len - 4],
+        _ => name,
+    }
+}
+
+/// Detect the frontmost application. Returns `None` when it can't be determined,
+/// e.g. on Wayland compositors that don't expose the focused window.
+pub fn frontmost_app() -> Option<ActiveApp> {
+    let app = detect()?;
+    (!app.is_empty()).then_some(app)
+}
+
+#[cfg(target_os = "macos")]
+fn detect() -> Option<ActiveApp> {
+    // lsappinfo ships with macOS and, unlike AppleScript, needs no automation permission
+    let front = run("lsappinfo", &["front"])?;
+    let info = run(
+        "lsappinfo",
+        &["info", "-only", "name", "-only", "bundleid", front.trim()],
+    )?;
+
+    Some(ActiveApp {
+        process_name: lsappinfo_value(&info, "LSDisplayName"),
+        window_class: None,
+        bundle_id: lsappinfo_value(&info, "CFBundleIdentifier"),
+    })
+}
+
+/// Extract `"key"="value"` from lsappinfo output
+#[cfg(any(target_os = "macos", test))]
+fn lsappinfo_value(output: &str, key: &str) -> Option<String> {
+    let prefix = format!("\"{}\"=", key);
+    output.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(&prefix)?.trim_matches('"');
+        (!value.is_empty() && value != "[ NULL ]").then(|| value.to_string())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn detect() -> Option<ActiveApp> {
+    if crate::utils::is_wayland() {
+        // Wayland has no portable way to query the focused window
+        return None;
+    }
+    if !xdotool_available() {
+        return None;
+    }
+
+    let window_class = run("xdotool", &["getactivewindow", "getwindowclassname"])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let process_name = run("xdotool", &["getactivewindow", "getwindowpid"])
+        .and_then(|pid| std::fs::read_to_string(format!("/proc/{}/comm", pid.trim())).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    Some(ActiveApp {
+        process_name,
+        window_class,
+        bundle_id: None,
+    })
+}
+
+/// Whether xdotool is installed. Without it app profiles can't see the focused
+/// window on X11, which is logged once so the fallback to the default settings
+/// isn't silent.
+#[cfg(target_os = "linux")]
+fn xdotool_available() -> bool {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    let available = Command::new("which")
+        .arg("xdotool")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !available && !WARNED.swap(true, Ordering::Relaxed) {
+        log::warn!(
+            "xdotool is not installed, so app profiles can't detect the focused application \
+             and the default settings are used. Install xdotool to enable them."
+        );
+    }
+    available
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "windows")]
+fn detect() -> Option<ActiveApp> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetClassNameW, GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return None;
+        }
+
+        let mut class = [0u16; 256];
+        let len = GetClassNameW(hwnd, &mut class);
+        let window_class = (len > 0).then(|| String::from_utf16_lossy(&class[..len as usize]));
+
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        let process_name = if pid != 0 {
+            process_image_name(pid)
+        } else {
+            None
+        };
+
+        Some(ActiveApp {
+            process_name,
+            window_class,
+            bundle_id: None,
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn process_image_name(pid: u32) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+    let mut buffer = [0u16; 1024];
+    let mut size = buffer.len() as u32;
+    let result = QueryFullProcessImageNameW(
+        handle,
+        PROCESS_NAME_WIN32,
+        PWSTR(buffer.as_mut_ptr()),
+        &mut size,
+    );
+    let _ = CloseHandle(handle);
+    result.ok()?;
+
+    let path = String::from_utf16_lossy(&buffer[..size as usize]);
+    std::path::Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn detect() -> Option<ActiveApp> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_any_identifier_ignoring_case_and_exe() {
+        let app = ActiveApp {
+            process_name: Some("Slack.exe".to_string()),
+            window_class: Some("Chrome_WidgetWin_1".to_string()),
+            bundle_id: None,
+        };
+        assert!(app.matches("slack"));
+        assert!(app.matches("SLACK.EXE"));
+        assert!(app.matches("chrome_widgetwin_1"));
+        assert!(!app.matches("slac"));
+        assert!(!app.matches("  "));
+    }
+
+    #[test]
+    fn parses_lsappinfo_output() {
+        let output =
+            "\"LSDisplayName\"=\"Slack\"\n\"CFBundleIdentifier\"=\"com.tinyspeck.slackmacgap\"\n";
+        assert_eq!(
+            lsappinfo_value(output, "LSDisplayName").as_deref(),
+            Some("Slack")
+        );
+        assert_eq!(
+            lsappinfo_value(output, "CFBundleIdentifier").as_deref(),
+            Some("com.tinyspeck.slackmacgap")
+        );
+        assert_eq!(lsappinfo_value(output, "LSBundlePath"), None);
+    }
+}
@@ -1 +1,2 @@
+pub mod active_app;
 pub mod clamshell;
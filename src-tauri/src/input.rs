@@ -121,3 +121,106 @@ pub fn paste_text_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Presses a space-separated sequence of key combos, such as `ctrl+a` or `enter enter`.
+pub fn send_key_sequence(enigo: &mut Enigo, sequence: &str) -> Result<(), String> {
+    for combo in parse_key_sequence(sequence)? {
+        let Some((key, modifiers)) = combo.split_last() else {
+            continue;
+        };
+
+        for modifier in modifiers {
+            enigo
+                .key(*modifier, enigo::Direction::Press)
+                .map_err(|e| format!("Failed to press {:?}: {}", modifier, e))?;
+        }
+        enigo
+            .key(*key, enigo::Direction::Click)
+            .map_err(|e| format!("Failed to click {:?}: {}", key, e))?;
+        for modifier in modifiers.iter().rev() {
+            enigo
+                .key(*modifier, enigo::Direction::Release)
+                .map_err(|e| format!("Failed to release {:?}: {}", modifier, e))?;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    Ok(())
+}
+
+/// Parses a key sequence into combos, each listing its modifiers followed by the key.
+pub fn parse_key_sequence(sequence: &str) -> Result<Vec<Vec<Key>>, String> {
+    let combos = sequence
+        .split_whitespace()
+        .map(|combo| combo.split('+').map(parse_key).collect())
+        .collect::<Result<Vec<Vec<Key>>, String>>()?;
+
+    if combos.is_empty() {
+        return Err("Key sequence cannot be empty".to_string());
+    }
+    Ok(combos)
+}
+
+fn parse_key(name: &str) -> Result<Key, String> {
+    let lower = name.trim().to_lowercase();
+    let key = match lower.as_str() {
+        "ctrl" | "control" => Key::Control,
+        "shift" => Key::Shift,
+        "alt" | "option" => Key::Alt,
+        "cmd" | "command" | "meta" | "super" | "win" => Key::Meta,
+        "enter" | "return" => Key::Return,
+        "tab" => Key::Tab,
+        "esc" | "escape" => Key::Escape,
+        "space" => Key::Space,
+        "backspace" => Key::Backspace,
+        "delete" | "del" => Key::Delete,
+        "up" => Key::UpArrow,
+        "down" => Key::DownArrow,
+        "left" => Key::LeftArrow,
+        "right" => Key::RightArrow,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "f1" => Key::F1,
+        "f2" => Key::F2,
+        "f3" => Key::F3,
+        "f4" => Key::F4,
+        "f5" => Key::F5,
+        "f6" => Key::F6,
+        "f7" => Key::F7,
+        "f8" => Key::F8,
+        "f9" => Key::F9,
+        "f10" => Key::F10,
+        "f11" => Key::F11,
+        "f12" => Key::F12,
+        _ => {
+            let mut chars = lower.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Key::Unicode(c),
+                _ => return Err(format!("Unknown key '{}'", name)),
+            }
+        }
+    };
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_sequences() {
+        assert_eq!(
+            parse_key_sequence("ctrl+shift+t").unwrap(),
+            vec![vec![Key::Control, Key::Shift, Key::Unicode('t')]]
+        );
+        assert_eq!(
+            parse_key_sequence("Enter  enter").unwrap(),
+            vec![vec![Key::Return], vec![Key::Return]]
+        );
+        assert!(parse_key_sequence("ctrl+hyper").is_err());
+        assert!(parse_key_sequence("  ").is_err());
+    }
+}
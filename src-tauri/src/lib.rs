@@ -3,8 +3,10 @@ mod actions;
 mod apple_intelligence;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod cli;
 mod clipboard;
 mod commands;
+mod control_socket;
 mod helpers;
 mod input;
 mod llm_client;
@@ -13,9 +15,11 @@ mod overlay;
 mod settings;
 mod shortcut;
 mod signal_handle;
+mod snippets;
 mod tray;
 mod tray_i18n;
 mod utils;
+mod voice_commands;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri_specta::{collect_commands, Builder};
 
@@ -59,22 +63,23 @@ fn level_filter_from_u8(value: u8) -> log::LevelFilter {
     }
 }
 
-fn build_console_filter() -> env_filter::Filter {
+fn build_console_filter(default_level: log::LevelFilter) -> env_filter::Filter {
     let mut builder = EnvFilterBuilder::new();
 
     match std::env::var("RUST_LOG") {
         Ok(spec) if !spec.trim().is_empty() => {
             if let Err(err) = builder.try_parse(&spec) {
                 log::warn!(
-                    "Ignoring invalid RUST_LOG value '{}': {}. Falling back to info-level console logging",
+                    "Ignoring invalid RUST_LOG value '{}': {}. Falling back to {}-level console logging",
                     spec,
-                    err
+                    err,
+                    default_level
                 );
-                builder.filter_level(log::LevelFilter::Info);
+                builder.filter_level(default_level);
             }
         }
         _ => {
-            builder.filter_level(log::LevelFilter::Info);
+            builder.filter_level(default_level);
         }
     }
 
@@ -122,12 +127,23 @@ pub(crate) async fn update_foundry_settings(
         .iter()
         .position(|p| p.id == "custom");
 
+    // Keep any timeout/retry tuning the user already applied to the custom provider
+    let (timeout_secs, max_retries, retry_on_timeout) = custom_provider_index
+        .map(|index| &settings.post_process_providers[index])
+        .map(|p| (p.timeout_secs, p.max_retries, p.retry_on_timeout))
+        .unwrap_or((None, crate::settings::default_provider_max_retries(), false));
+
     let new_custom_provider = crate::settings::PostProcessProvider {
         id: "custom".to_string(),
         label: "Custom".to_string(),
         base_url: endpoint_url,
         allow_base_url_edit: true,
         models_endpoint: Some("/models".to_string()),
+        // Foundry Local serves an OpenAI-compatible API
+        protocol: crate::settings::ProviderProtocol::OpenaiChat,
+        timeout_secs,
+        max_retries,
+        retry_on_timeout,
     };
 
     if let Some(index) = custom_provider_index {
@@ -249,6 +265,16 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
 
+    // Pick up models published to the remote catalog since the last launch
+    if settings::get_settings(app_handle).model_catalog_url.is_some() {
+        let model_manager = model_manager.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = model_manager.refresh_catalog().await {
+                log::warn!("Failed to refresh the model catalog: {}", e);
+            }
+        });
+    }
+
     // Note: Shortcuts are NOT initialized here.
     // The frontend is responsible for calling the `initialize_shortcuts` command
     // after permissions are confirmed (on macOS) or after onboarding completes.
@@ -260,6 +286,11 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     #[cfg(unix)]
     signal_handle::setup_signal_handler(app_handle.clone(), signals);
 
+    // Start the local control socket so scripts can drive dictation
+    if settings::get_settings(app_handle).control_socket_enabled {
+        control_socket::start_server(app_handle);
+    }
+
     // Apply macOS Accessory policy if starting hidden
     #[cfg(target_os = "macos")]
     {
@@ -346,11 +377,73 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+fn app_context() -> tauri::Context<tauri::Wry> {
+    tauri::generate_context!()
+}
+
+/// Runs a headless CLI command (e.g. `handy transcribe <file>`) if one was given on the
+/// command line. Returns the process exit code, or `None` when the app should start normally.
+pub fn run_cli() -> Option<i32> {
+    let command = cli::parse_args(std::env::args())?;
+    cli::attach_parent_console();
+
+    let command = match command {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}\n", e);
+            cli::print_usage();
+            return Some(2);
+        }
+    };
+
+    let args = match command {
+        cli::CliCommand::Help => {
+            cli::print_usage();
+            return Some(0);
+        }
+        cli::CliCommand::Transcribe(args) => args,
+    };
+
+    // Logs go to stderr so stdout only carries the transcription
+    let console_filter = build_console_filter(log::LevelFilter::Warn);
+    let mut context = app_context();
+    context.config_mut().app.windows.clear();
+
+    let app = match tauri::Builder::default()
+        .plugin(
+            LogBuilder::new()
+                .level(log::LevelFilter::Trace)
+                .clear_targets()
+                .target(
+                    Target::new(TargetKind::Stderr)
+                        .filter(move |metadata| console_filter.enabled(metadata)),
+                )
+                .build(),
+        )
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .build(context)
+    {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Error: failed to initialize Handy: {}", e);
+            return Some(1);
+        }
+    };
+
+    match cli::run_transcribe(app.handle(), &args) {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            Some(1)
+        }
+    }
+}
+
 #[tokio::main]
 pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Parse console logging directives from RUST_LOG, falling back to info-level logging
     // when the variable is unset
-    let console_filter = build_console_filter();
+    let console_filter = build_console_filter(log::LevelFilter::Info);
 
     let specta_builder = Builder::<tauri::Wry>::new().commands(collect_commands![
         shortcut::change_binding,
@@ -366,11 +459,27 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
         shortcut::change_overlay_position_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
+        shortcut::change_spoken_punctuation_setting,
+        shortcut::update_spoken_punctuation,
+        shortcut::get_builtin_spoken_punctuation,
+        shortcut::change_filler_word_filter_setting,
+        shortcut::update_filler_words,
+        shortcut::get_builtin_filler_words,
+        shortcut::update_replacement_rules,
+        shortcut::preview_replacement_rules,
         shortcut::change_paste_method_setting,
         shortcut::change_clipboard_handling_setting,
         shortcut::change_post_process_enabled_setting,
+        shortcut::change_voice_commands_enabled_setting,
+        shortcut::change_voice_command_threshold_setting,
+        shortcut::add_voice_command,
+        shortcut::update_voice_command,
+        shortcut::delete_voice_command,
         shortcut::change_experimental_enabled_setting,
         shortcut::change_post_process_base_url_setting,
+        shortcut::change_post_process_protocol_setting,
+        shortcut::change_post_process_request_setting,
+        shortcut::set_post_process_fallback_providers,
         shortcut::change_post_process_api_key_setting,
         shortcut::change_post_process_model_setting,
         shortcut::set_post_process_provider,
@@ -378,12 +487,26 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
         shortcut::add_post_process_prompt,
         shortcut::update_post_process_prompt,
         shortcut::delete_post_process_prompt,
+        shortcut::add_app_profile,
+        shortcut::update_app_profile,
+        shortcut::delete_app_profile,
         shortcut::set_post_process_selected_prompt,
+        shortcut::set_post_process_language_prompt,
         shortcut::update_custom_words,
+        shortcut::add_snippet,
+        shortcut::update_snippet,
+        shortcut::delete_snippet,
+        shortcut::export_snippets,
+        shortcut::import_snippets,
         shortcut::suspend_binding,
+        shortcut::add_transcribe_binding,
+        shortcut::update_transcribe_binding,
+        shortcut::delete_transcribe_binding,
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
         shortcut::change_append_trailing_space_setting,
+        shortcut::change_streaming_transcription_setting,
+        shortcut::change_control_socket_setting,
         shortcut::change_app_language_setting,
         shortcut::change_update_checks_setting,
         shortcut::change_keyboard_implementation_setting,
@@ -414,12 +537,25 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
         commands::models::download_model,
         commands::models::delete_model,
         commands::models::cancel_download,
+        commands::models::pause_download,
+        commands::models::resume_download,
+        commands::models::prioritize_download,
+        commands::models::get_download_queue,
+        commands::models::set_max_concurrent_downloads,
+        commands::models::set_download_speed_limit,
         commands::models::set_active_model,
         commands::models::get_current_model,
         commands::models::get_transcription_model_status,
         commands::models::is_model_loading,
         commands::models::has_any_models_available,
         commands::models::has_any_models_or_downloads,
+        commands::models::refresh_model_catalog,
+        commands::models::import_model,
+        commands::models::verify_model,
+        commands::models::set_model_catalog_url,
+        commands::models::set_model_mirror_url,
+        commands::models::set_model_download_proxy,
+        commands::models::install_model_bundle,
         commands::audio::update_microphone_mode,
         commands::audio::get_microphone_mode,
         commands::audio::get_available_microphones,
@@ -437,9 +573,14 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
         commands::history::get_history_entries,
+        commands::history::get_history_page,
+        commands::history::search_history,
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
+        commands::history::export_history,
+        commands::history::get_history_revisions,
+        commands::history::retranscribe_history_entry,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
         helpers::clamshell::is_laptop,
@@ -555,6 +696,6 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
             _ => {}
         })
         .invoke_handler(specta_builder.invoke_handler())
-        .run(tauri::generate_context!())?; // Removed expect and Ok(()), return Result
+        .run(app_context())?; // Removed expect and Ok(()), return Result
     Ok(())
 }
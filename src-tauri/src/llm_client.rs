@@ -1,18 +1,39 @@
-use crate::settings::PostProcessProvider;
-use log::debug;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
+use crate::settings::{LLMPrompt, PostProcessProvider, ProviderProtocol};
+use futures_util::StreamExt;
+use log::{debug, warn};
+use reqwest::header::{
+    HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, RETRY_AFTER, USER_AGENT,
+};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
+/// Variable replaced with the transcription (or an example input) in prompt templates.
+const OUTPUT_VARIABLE: &str = "output";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
 }
 
+impl ChatMessage {
+    fn new(role: &str, content: String) -> Self {
+        Self {
+            role: role.to_string(),
+            content,
+        }
+    }
+}
+
+// OpenAI chat completions (`/chat/completions`)
+
 #[derive(Debug, Serialize)]
 struct ChatCompletionRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +51,378 @@ struct ChatMessageResponse {
     content: Option<String>,
 }
 
+/// One `data:` event of a streamed (`stream: true`) chat completion
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    choices: Vec<ChatChunkChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunkChoice {
+    delta: ChatMessageResponse,
+}
+
+// Anthropic Messages (`/messages`)
+
+/// Anthropic requires an explicit output limit on every request
+const ANTHROPIC_MAX_TOKENS: u32 = 4096;
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    text: Option<String>,
+}
+
+/// Streamed events; only text deltas, the stop marker and errors matter here
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    delta: Option<AnthropicDelta>,
+    error: Option<AnthropicError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicDelta {
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    message: String,
+}
+
+// Ollama native chat (`/api/chat`)
+
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    // Ollama streams unless told otherwise, so this is always sent
+    stream: bool,
+}
+
+/// Both the full response and each streamed line
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    message: Option<ChatMessageResponse>,
+    #[serde(default)]
+    done: bool,
+}
+
+// Plain text completions (`/completions`)
+
+#[derive(Debug, Serialize)]
+struct TextCompletionRequest {
+    model: String,
+    prompt: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+/// Both the full response and each streamed event
+#[derive(Debug, Deserialize)]
+struct TextCompletionResponse {
+    choices: Vec<TextChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextChoice {
+    text: Option<String>,
+}
+
+/// What a single streamed payload means for the caller
+#[derive(Debug, PartialEq)]
+enum StreamEvent {
+    Delta(String),
+    Done,
+    Ignore,
+}
+
+/// Full URL of the completion endpoint for the provider's protocol
+fn completion_url(provider: &PostProcessProvider) -> String {
+    let base_url = provider.base_url.trim_end_matches('/');
+    match provider.protocol {
+        ProviderProtocol::OpenaiChat => format!("{}/chat/completions", base_url),
+        ProviderProtocol::AnthropicMessages => format!("{}/messages", base_url),
+        ProviderProtocol::OllamaChat => format!("{}/api/chat", ollama_root(base_url)),
+        ProviderProtocol::TextCompletion => format!("{}/completions", base_url),
+    }
+}
+
+/// Ollama's native API lives at the server root, its OpenAI-compatible one under `/v1`,
+/// so either form of base URL works.
+fn ollama_root(base_url: &str) -> &str {
+    base_url.strip_suffix("/v1").unwrap_or(base_url)
+}
+
+fn build_request_body(
+    protocol: ProviderProtocol,
+    model: &str,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+) -> Result<serde_json::Value, String> {
+    let model = model.to_string();
+    let body = match protocol {
+        ProviderProtocol::OpenaiChat => serde_json::to_value(ChatCompletionRequest {
+            model,
+            messages,
+            stream,
+        }),
+        ProviderProtocol::AnthropicMessages => {
+            // System instructions are a top-level field rather than a message
+            let (system, messages): (Vec<ChatMessage>, Vec<ChatMessage>) =
+                messages.into_iter().partition(|m| m.role == "system");
+            let system = Some(flatten_messages(&system)).filter(|s| !s.is_empty());
+            serde_json::to_value(AnthropicRequest {
+                model,
+                max_tokens: ANTHROPIC_MAX_TOKENS,
+                system,
+                messages,
+                stream,
+            })
+        }
+        ProviderProtocol::OllamaChat => serde_json::to_value(OllamaChatRequest {
+            model,
+            messages,
+            stream,
+        }),
+        ProviderProtocol::TextCompletion => serde_json::to_value(TextCompletionRequest {
+            model,
+            prompt: completion_prompt(&messages),
+            stream,
+        }),
+    };
+    body.map_err(|e| format!("Failed to build request: {}", e))
+}
+
+/// Extract the generated text from a complete (non-streamed) response body
+fn parse_response(protocol: ProviderProtocol, body: &str) -> Result<Option<String>, String> {
+    let parse_error = |e: serde_json::Error| format!("Failed to parse API response: {}", e);
+    let content = match protocol {
+        ProviderProtocol::OpenaiChat => serde_json::from_str::<ChatCompletionResponse>(body)
+            .map_err(parse_error)?
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content),
+        ProviderProtocol::AnthropicMessages => {
+            let text: String = serde_json::from_str::<AnthropicResponse>(body)
+                .map_err(parse_error)?
+                .content
+                .into_iter()
+                .filter(|block| block.kind == "text")
+                .filter_map(|block| block.text)
+                .collect();
+            Some(text).filter(|t| !t.is_empty())
+        }
+        ProviderProtocol::OllamaChat => serde_json::from_str::<OllamaChatResponse>(body)
+            .map_err(parse_error)?
+            .message
+            .and_then(|message| message.content),
+        ProviderProtocol::TextCompletion => serde_json::from_str::<TextCompletionResponse>(body)
+            .map_err(parse_error)?
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.text),
+    };
+    Ok(content)
+}
+
+/// Interpret one streamed payload (an SSE `data:` value, or an NDJSON line for Ollama)
+fn parse_stream_event(protocol: ProviderProtocol, payload: &str) -> Result<StreamEvent, String> {
+    let parse_error = |e: serde_json::Error| format!("Failed to parse stream event: {}", e);
+    let delta = match protocol {
+        ProviderProtocol::OpenaiChat | ProviderProtocol::TextCompletion if payload == "[DONE]" => {
+            return Ok(StreamEvent::Done)
+        }
+        ProviderProtocol::OpenaiChat => serde_json::from_str::<ChatCompletionChunk>(payload)
+            .map_err(parse_error)?
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.delta.content),
+        ProviderProtocol::TextCompletion => serde_json::from_str::<TextCompletionResponse>(payload)
+            .map_err(parse_error)?
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.text),
+        ProviderProtocol::AnthropicMessages => {
+            let event: AnthropicStreamEvent = serde_json::from_str(payload).map_err(parse_error)?;
+            match event.kind.as_str() {
+                "message_stop" => return Ok(StreamEvent::Done),
+                "error" => {
+                    return Err(event
+                        .error
+                        .map(|e| e.message)
+                        .unwrap_or_else(|| "Unknown streaming error".to_string()))
+                }
+                _ => event.delta.and_then(|delta| delta.text),
+            }
+        }
+        ProviderProtocol::OllamaChat => {
+            let chunk: OllamaChatResponse = serde_json::from_str(payload).map_err(parse_error)?;
+            if chunk.done {
+                return Ok(StreamEvent::Done);
+            }
+            chunk.message.and_then(|message| message.content)
+        }
+    };
+
+    Ok(match delta {
+        Some(delta) if !delta.is_empty() => StreamEvent::Delta(delta),
+        _ => StreamEvent::Ignore,
+    })
+}
+
+/// Splits a response stream into lines. Network chunks can end mid-line (or
+/// mid-character), so incomplete lines are kept until more bytes arrive.
+#[derive(Default)]
+struct LineDecoder {
+    buffer: Vec<u8>,
+}
+
+impl LineDecoder {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            lines.push(String::from_utf8_lossy(&line).trim_end().to_string());
+        }
+        lines
+    }
+}
+
+/// The payload carried by a stream line: `data:` values for server-sent events,
+/// every non-empty line for Ollama's newline-delimited JSON.
+fn stream_payload(protocol: ProviderProtocol, line: &str) -> Option<&str> {
+    match protocol {
+        ProviderProtocol::OllamaChat => Some(line).filter(|l| !l.is_empty()),
+        _ => line.strip_prefix("data:").map(str::trim_start),
+    }
+}
+
+/// Substitute `${output}` and the prompt's extra variables in a single pass over the
+/// template, so substituted values (including the transcription) are never expanded
+/// again. Unknown variables are left as they are.
+fn render_template(template: &str, prompt: &LLMPrompt, output: &str) -> String {
+    let mut rendered = String::with_capacity(template.len() + output.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let name = &after[..end];
+        let value = if name == OUTPUT_VARIABLE {
+            Some(output)
+        } else {
+            prompt.variables.get(name).map(String::as_str)
+        };
+        match value {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + end + 3]),
+        }
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Build the message list for a post-processing prompt: an optional system message,
+/// one user/assistant pair per example, then the transcription itself.
+pub fn build_messages(prompt: &LLMPrompt, transcription: &str) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+
+    if let Some(system_prompt) = prompt
+        .system_prompt
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        messages.push(ChatMessage::new(
+            "system",
+            render_template(system_prompt, prompt, ""),
+        ));
+    }
+
+    // Without a template the transcription is sent as-is, which is what most
+    // system-prompt style setups expect
+    let user_message = |text: &str| {
+        if prompt.prompt.trim().is_empty() {
+            text.to_string()
+        } else {
+            render_template(&prompt.prompt, prompt, text)
+        }
+    };
+
+    for example in &prompt.examples {
+        messages.push(ChatMessage::new("user", user_message(&example.input)));
+        messages.push(ChatMessage::new("assistant", example.output.clone()));
+    }
+
+    messages.push(ChatMessage::new("user", user_message(transcription)));
+    messages
+}
+
+/// Join the contents of messages that share a role, e.g. Anthropic's system field.
+fn flatten_messages(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Turn a message list into a single prompt for backends without chat roles. Each
+/// message is labelled with its role and the prompt ends with an open `Assistant:`
+/// turn, so few-shot examples keep their input/output structure. A lone user
+/// message is sent as it is.
+pub fn completion_prompt(messages: &[ChatMessage]) -> String {
+    if let [message] = messages {
+        if message.role == "user" {
+            return message.content.clone();
+        }
+    }
+
+    let mut prompt = String::new();
+    for message in messages {
+        let label = match message.role.as_str() {
+            "system" => "System",
+            "assistant" => "Assistant",
+            _ => "User",
+        };
+        prompt.push_str(&format!("{}: {}\n\n", label, message.content));
+    }
+    prompt.push_str("Assistant:");
+    prompt
+}
+
 /// Build headers for API requests based on provider type
 fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
@@ -46,15 +439,21 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
     );
     headers.insert("X-Title", HeaderValue::from_static("Handy"));
 
+    if provider.protocol == ProviderProtocol::AnthropicMessages {
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+    }
+
     // Provider-specific auth headers
     if !api_key.is_empty() {
-        if provider.id == "anthropic" {
+        if provider.protocol == ProviderProtocol::AnthropicMessages {
             headers.insert(
                 "x-api-key",
                 HeaderValue::from_str(api_key)
                     .map_err(|e| format!("Invalid API key header value: {}", e))?,
             );
-            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
         } else {
             headers.insert(
                 AUTHORIZATION,
@@ -76,75 +475,233 @@ fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwes
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
 }
 
-/// Send a chat completion request to an OpenAI-compatible API
+/// Delay before the first retry; doubled for every further attempt
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+/// Upper bound for a single wait, including a server-provided `Retry-After`
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Rate limits and server errors are usually transient, anything else won't change on retry
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after
+        .unwrap_or_else(|| BASE_BACKOFF.saturating_mul(2u32.saturating_pow(attempt)))
+        .min(MAX_BACKOFF)
+}
+
+/// `Retry-After` in seconds; the HTTP-date form is rare for these APIs and ignored
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Runs `future` within the provider's timeout, if it has one. `None` means it timed out.
+async fn within_timeout<T>(
+    provider: &PostProcessProvider,
+    future: impl std::future::Future<Output = T>,
+) -> Option<T> {
+    match provider.timeout_secs {
+        Some(secs) => tokio::time::timeout(Duration::from_secs(secs), future)
+            .await
+            .ok(),
+        None => Some(future.await),
+    }
+}
+
+/// POST `body` to `url`, retrying connection errors, 429 and 5xx responses with
+/// exponential backoff up to the provider's `max_retries`. Timeouts are only retried
+/// with `retry_on_timeout`. Only successful responses are returned. For `stream`
+/// requests the timeout covers the wait for response headers; reading the body is
+/// the caller's business.
+async fn post_with_retry(
+    client: &reqwest::Client,
+    provider: &PostProcessProvider,
+    url: &str,
+    body: &serde_json::Value,
+    stream: bool,
+) -> Result<reqwest::Response, String> {
+    let mut attempt = 0;
+
+    loop {
+        let mut request = client.post(url).json(body);
+        if let Some(secs) = provider.timeout_secs.filter(|_| !stream) {
+            request = request.timeout(Duration::from_secs(secs));
+        }
+
+        let (error, server_delay) = match within_timeout(provider, request.send()).await {
+            Some(Ok(response)) if response.status().is_success() => return Ok(response),
+            Some(Ok(response)) => {
+                let status = response.status();
+                let server_delay = retry_after(&response);
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Failed to read error response".to_string());
+                let error = format!("API request failed with status {}: {}", status, error_text);
+                if !is_retryable_status(status) {
+                    return Err(error);
+                }
+                (error, server_delay)
+            }
+            Some(Err(e)) => {
+                let error = format!("HTTP request failed: {}", e);
+                let retryable = if e.is_timeout() {
+                    provider.retry_on_timeout
+                } else {
+                    e.is_connect()
+                };
+                if !retryable {
+                    return Err(error);
+                }
+                (error, None)
+            }
+            None => {
+                let error = format!(
+                    "Request timed out after {}s",
+                    provider.timeout_secs.unwrap_or_default()
+                );
+                if !provider.retry_on_timeout {
+                    return Err(error);
+                }
+                (error, None)
+            }
+        };
+
+        if attempt >= provider.max_retries {
+            return Err(error);
+        }
+        let delay = backoff_delay(attempt, server_delay);
+        warn!(
+            "{} (attempt {}/{}), retrying in {:?}",
+            error,
+            attempt + 1,
+            provider.max_retries + 1,
+            delay
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Send a chat completion request using the provider's protocol
 /// Returns Ok(Some(content)) on success, Ok(None) if response has no content,
 /// or Err on actual errors (HTTP, parsing, etc.)
 pub async fn send_chat_completion(
     provider: &PostProcessProvider,
     api_key: String,
     model: &str,
-    prompt: String,
+    messages: Vec<ChatMessage>,
 ) -> Result<Option<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/chat/completions", base_url);
+    let url = completion_url(provider);
 
-    debug!("Sending chat completion request to: {}", url);
+    debug!(
+        "Sending {:?} completion request to: {}",
+        provider.protocol, url
+    );
 
     let client = create_client(provider, &api_key)?;
 
-    let request_body = ChatCompletionRequest {
-        model: model.to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: prompt,
-        }],
-    };
+    let request_body = build_request_body(provider.protocol, model, messages, false)?;
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
+    let response = post_with_retry(&client, provider, &url, &request_body, false).await?;
+
+    let body = response
+        .text()
         .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read error response".to_string());
-        return Err(format!(
-            "API request failed with status {}: {}",
-            status, error_text
-        ));
-    }
+    parse_response(provider.protocol, &body)
+}
 
-    let completion: ChatCompletionResponse = response
-        .json()
+/// Send a streaming chat completion request using the provider's protocol.
+/// `on_delta` is called with each piece of content as it arrives and returns `false`
+/// to abort the request. Returns the full content, like [`send_chat_completion`].
+pub async fn stream_chat_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    messages: Vec<ChatMessage>,
+    mut on_delta: impl FnMut(&str) -> bool,
+) -> Result<Option<String>, String> {
+    let url = completion_url(provider);
+
+    debug!(
+        "Sending streaming {:?} completion request to: {}",
+        provider.protocol, url
+    );
+
+    let client = create_client(provider, &api_key)?;
+
+    let request_body = build_request_body(provider.protocol, model, messages, true)?;
+
+    let response = post_with_retry(&client, provider, &url, &request_body, true).await?;
+
+    let mut content = String::new();
+    let mut decoder = LineDecoder::default();
+    let mut stream = response.bytes_stream();
+
+    // The timeout applies between chunks, so long completions are fine while tokens keep coming
+    while let Some(bytes) = within_timeout(provider, stream.next())
         .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
+        .ok_or_else(|| {
+            format!(
+                "No data received for {}s",
+                provider.timeout_secs.unwrap_or_default()
+            )
+        })?
+    {
+        let bytes = bytes.map_err(|e| format!("Failed to read response stream: {}", e))?;
+        for line in decoder.feed(&bytes) {
+            let Some(payload) = stream_payload(provider.protocol, &line) else {
+                continue;
+            };
+            let delta = match parse_stream_event(provider.protocol, payload)? {
+                StreamEvent::Delta(delta) => delta,
+                StreamEvent::Done => return Ok(Some(content).filter(|c| !c.is_empty())),
+                StreamEvent::Ignore => continue,
+            };
+
+            content.push_str(&delta);
+            if !on_delta(&delta) {
+                // Dropping the stream closes the connection
+                return Err("Streaming completion cancelled".to_string());
+            }
+        }
+    }
 
-    Ok(completion
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.clone()))
+    Ok(Some(content).filter(|c| !c.is_empty()))
 }
 
-/// Fetch available models from an OpenAI-compatible API
+/// Fetch available models from the provider
 /// Returns a list of model IDs
 pub async fn fetch_models(
     provider: &PostProcessProvider,
     api_key: String,
 ) -> Result<Vec<String>, String> {
     let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/models", base_url);
+    let url = match provider.protocol {
+        ProviderProtocol::OllamaChat => format!("{}/api/tags", ollama_root(base_url)),
+        _ => format!("{}/models", base_url),
+    };
 
     debug!("Fetching models from: {}", url);
 
     let client = create_client(provider, &api_key)?;
 
-    let response = client
-        .get(&url)
+    let mut request = client.get(&url);
+    if let Some(secs) = provider.timeout_secs {
+        request = request.timeout(Duration::from_secs(secs));
+    }
+    let response = request
         .send()
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
@@ -178,6 +735,14 @@ pub async fn fetch_models(
             }
         }
     }
+    // Handle Ollama format: { models: [ { name: "..." }, ... ] }
+    else if let Some(list) = parsed.get("models").and_then(|m| m.as_array()) {
+        for entry in list {
+            if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                models.push(name.to_string());
+            }
+        }
+    }
     // Handle array format: [ "model1", "model2", ... ]
     else if let Some(array) = parsed.as_array() {
         for entry in array {
@@ -189,3 +754,225 @@ pub async fn fetch_models(
 
     Ok(models)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::PromptExample;
+    use std::collections::HashMap;
+
+    fn prompt(template: &str, system_prompt: Option<&str>) -> LLMPrompt {
+        LLMPrompt {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            prompt: template.to_string(),
+            system_prompt: system_prompt.map(str::to_string),
+            examples: Vec::new(),
+            variables: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn legacy_prompt_is_a_single_user_message() {
+        let messages = build_messages(&prompt("Fix this: ${output}", None), "hello world");
+        assert_eq!(
+            messages,
+            vec![ChatMessage::new(
+                "user",
+                "Fix this: hello world".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn system_prompt_examples_and_variables() {
+        let mut p = prompt("", Some("You clean up dictation for ${audience}."));
+        p.variables
+            .insert("audience".to_string(), "engineers".to_string());
+        p.examples.push(PromptExample {
+            input: "um so the build is broken".to_string(),
+            output: "The build is broken.".to_string(),
+        });
+
+        let messages = build_messages(&p, "the ${audience} test passed");
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+        assert_eq!(messages[0].content, "You clean up dictation for engineers.");
+        assert_eq!(messages[1].content, "um so the build is broken");
+        // Variables are never expanded inside the transcription itself
+        assert_eq!(messages[3].content, "the ${audience} test passed");
+    }
+
+    #[test]
+    fn template_variables_are_substituted_once() {
+        let mut p = prompt("${a} ${b} ${unknown} ${output} ${", None);
+        p.variables.insert("a".to_string(), "${b}".to_string());
+        p.variables.insert("b".to_string(), "${a}".to_string());
+        p.variables
+            .insert("output".to_string(), "not the transcription".to_string());
+        assert_eq!(
+            render_template(&p.prompt, &p, "said ${a}"),
+            "${b} ${a} ${unknown} said ${a} ${"
+        );
+    }
+
+    #[test]
+    fn completion_prompt_keeps_roles() {
+        let mut p = prompt("Fix: ${output}", Some("Clean up dictation."));
+        p.examples.push(PromptExample {
+            input: "um hi".to_string(),
+            output: "Hi.".to_string(),
+        });
+        assert_eq!(
+            completion_prompt(&build_messages(&p, "so yeah")),
+            "System: Clean up dictation.\n\nUser: Fix: um hi\n\nAssistant: Hi.\n\n\
+             User: Fix: so yeah\n\nAssistant:"
+        );
+
+        let legacy = build_messages(&prompt("Fix: ${output}", None), "so yeah");
+        assert_eq!(completion_prompt(&legacy), "Fix: so yeah");
+    }
+
+    #[test]
+    fn line_decoder_handles_split_events() {
+        let mut decoder = LineDecoder::default();
+        assert!(decoder.feed(b"data: {\"a\":").is_empty());
+        let lines = decoder.feed(b"1}\n\n: keep-alive\ndata: [DONE]\n");
+        let payloads: Vec<&str> = lines
+            .iter()
+            .filter_map(|l| stream_payload(ProviderProtocol::OpenaiChat, l))
+            .collect();
+        assert_eq!(payloads, vec!["{\"a\":1}", "[DONE]"]);
+
+        // A multi-byte character split across network chunks
+        let event = "data: {\"c\":\"\u{e9}\"}\n".as_bytes();
+        assert!(decoder.feed(&event[..13]).is_empty());
+        assert_eq!(
+            decoder.feed(&event[13..]),
+            vec!["data: {\"c\":\"\u{e9}\"}".to_string()]
+        );
+    }
+
+    #[test]
+    fn retries_only_transient_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        assert_eq!(backoff_delay(0, None), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2, None), Duration::from_secs(2));
+        assert_eq!(backoff_delay(10, None), MAX_BACKOFF);
+        assert_eq!(
+            backoff_delay(0, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            backoff_delay(0, Some(Duration::from_secs(120))),
+            MAX_BACKOFF
+        );
+    }
+
+    #[test]
+    fn anthropic_request_moves_system_prompt_to_top_level() {
+        let messages = vec![
+            ChatMessage::new("system", "Be terse.".to_string()),
+            ChatMessage::new("user", "hello".to_string()),
+        ];
+        let body = build_request_body(
+            ProviderProtocol::AnthropicMessages,
+            "claude-haiku",
+            messages,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(body["system"], "Be terse.");
+        assert_eq!(body["max_tokens"], ANTHROPIC_MAX_TOKENS);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert!(body.get("stream").is_none());
+    }
+
+    #[test]
+    fn ollama_request_always_sets_stream() {
+        let body =
+            build_request_body(ProviderProtocol::OllamaChat, "llama3", Vec::new(), false).unwrap();
+        assert_eq!(body["stream"], false);
+    }
+
+    #[test]
+    fn parses_each_protocol_response() {
+        let cases = [
+            (
+                ProviderProtocol::OpenaiChat,
+                r#"{"choices":[{"message":{"content":"openai"}}]}"#,
+                "openai",
+            ),
+            (
+                ProviderProtocol::AnthropicMessages,
+                r#"{"content":[{"type":"text","text":"anthro"},{"type":"text","text":"pic"}]}"#,
+                "anthropic",
+            ),
+            (
+                ProviderProtocol::OllamaChat,
+                r#"{"message":{"role":"assistant","content":"ollama"},"done":true}"#,
+                "ollama",
+            ),
+            (
+                ProviderProtocol::TextCompletion,
+                r#"{"choices":[{"text":"plain"}]}"#,
+                "plain",
+            ),
+        ];
+        for (protocol, body, expected) in cases {
+            assert_eq!(
+                parse_response(protocol, body).unwrap().as_deref(),
+                Some(expected),
+                "{:?}",
+                protocol
+            );
+        }
+    }
+
+    #[test]
+    fn parses_stream_events() {
+        use ProviderProtocol::*;
+
+        assert_eq!(
+            parse_stream_event(OpenaiChat, r#"{"choices":[{"delta":{"content":"Hi"}}]}"#),
+            Ok(StreamEvent::Delta("Hi".to_string()))
+        );
+        assert_eq!(
+            parse_stream_event(OpenaiChat, "[DONE]"),
+            Ok(StreamEvent::Done)
+        );
+        assert_eq!(
+            parse_stream_event(
+                AnthropicMessages,
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#
+            ),
+            Ok(StreamEvent::Delta("Hi".to_string()))
+        );
+        assert_eq!(
+            parse_stream_event(AnthropicMessages, r#"{"type":"ping"}"#),
+            Ok(StreamEvent::Ignore)
+        );
+        assert_eq!(
+            parse_stream_event(AnthropicMessages, r#"{"type":"message_stop"}"#),
+            Ok(StreamEvent::Done)
+        );
+        assert!(parse_stream_event(
+            AnthropicMessages,
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+        )
+        .is_err());
+        assert_eq!(
+            parse_stream_event(OllamaChat, r#"{"message":{"content":""},"done":true}"#),
+            Ok(StreamEvent::Done)
+        );
+    }
+}
@@ -12,5 +12,9 @@ fn main() {
         }
     }
 
+    if let Some(code) = handy_app_lib::run_cli() {
+        std::process::exit(code);
+    }
+
     handy_app_lib::run().expect("Failed to run Handy app");
 }
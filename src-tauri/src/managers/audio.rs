@@ -1,5 +1,6 @@
 use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
 use crate::helpers::clamshell;
+use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, AppSettings};
 use crate::utils;
 use log::{debug, error, info};
@@ -123,7 +124,7 @@ fn create_audio_recorder(
     let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
 
     // Recorder with VAD plus a spectrum-level callback that forwards updates to
-    // the frontend.
+    // the frontend, and a speech-chunk callback that feeds streaming transcription.
     let recorder = AudioRecorder::new()
         .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
         .with_vad(Box::new(smoothed_vad))
@@ -132,6 +133,15 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_speech_chunk_callback({
+            let app_handle = app_handle.clone();
+            move |chunk| {
+                // The transcription manager is registered after the recorder is created
+                if let Some(tm) = app_handle.try_state::<Arc<TranscriptionManager>>() {
+                    tm.push_stream_chunk(chunk);
+                }
+            }
         });
 
     Ok(recorder)
@@ -421,6 +431,14 @@ impl AudioRecordingManager {
         )
     }
 
+    /// The binding that started the current recording, if any
+    pub fn active_binding(&self) -> Option<String> {
+        match &*self.state.lock().unwrap() {
+            RecordingState::Recording { binding_id } => Some(binding_id.clone()),
+            _ => None,
+        }
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
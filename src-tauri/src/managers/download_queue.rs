@@ -0,0 +1,266 @@
+//! Bookkeeping for queued model downloads and the shared download speed limit.
+//! `ModelManager` owns both and starts the actual transfers.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Snapshot of the download queue, emitted as `model-download-queue`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct DownloadQueueState {
+    /// Downloads currently transferring
+    pub active: Vec<String>,
+    /// Downloads waiting for a free slot, next first
+    pub queued: Vec<String>,
+    /// Downloads stopped by the user, kept as `.partial` files to resume from
+    pub paused: Vec<String>,
+}
+
+/// What `DownloadQueue::pause` found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PausedFrom {
+    Queued,
+    /// The caller has to stop the running transfer
+    Active,
+    NotQueued,
+}
+
+#[derive(Debug, Default)]
+pub struct DownloadQueue {
+    queued: VecDeque<String>,
+    /// Includes paused downloads whose transfer hasn't stopped yet
+    active: Vec<String>,
+    paused: Vec<String>,
+}
+
+impl DownloadQueue {
+    /// Adds a download to the back of the queue, or resumes it if it was paused.
+    /// Returns false if it is already queued or running.
+    pub fn enqueue(&mut self, model_id: &str) -> bool {
+        if self.paused.iter().any(|id| id == model_id) {
+            return self.resume(model_id);
+        }
+        if self.queued.iter().any(|id| id == model_id) || self.is_running(model_id) {
+            return false;
+        }
+        self.queued.push_back(model_id.to_string());
+        true
+    }
+
+    /// Moves a queued download to the front of the queue.
+    pub fn prioritize(&mut self, model_id: &str) -> bool {
+        match self.queued.iter().position(|id| id == model_id) {
+            Some(index) => {
+                let id = self.queued.remove(index).unwrap();
+                self.queued.push_front(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes the next download to start, if fewer than `max_concurrent` are running.
+    /// A download that is still stopping after a pause is skipped until it has.
+    pub fn next_to_start(&mut self, max_concurrent: usize) -> Option<String> {
+        if self.active.len() >= max_concurrent {
+            return None;
+        }
+        let index = self
+            .queued
+            .iter()
+            .position(|id| !self.active.contains(id))?;
+        let model_id = self.queued.remove(index)?;
+        self.active.push(model_id.clone());
+        Some(model_id)
+    }
+
+    /// Marks a transfer as stopped, whether it completed, failed or was paused.
+    pub fn finish(&mut self, model_id: &str) {
+        self.active.retain(|id| id != model_id);
+    }
+
+    pub fn pause(&mut self, model_id: &str) -> PausedFrom {
+        if self.paused.iter().any(|id| id == model_id) {
+            return PausedFrom::NotQueued;
+        }
+        let from = if let Some(index) = self.queued.iter().position(|id| id == model_id) {
+            self.queued.remove(index);
+            PausedFrom::Queued
+        } else if self.active.iter().any(|id| id == model_id) {
+            PausedFrom::Active
+        } else {
+            return PausedFrom::NotQueued;
+        };
+        self.paused.push(model_id.to_string());
+        from
+    }
+
+    /// Puts a paused download back at the end of the queue.
+    pub fn resume(&mut self, model_id: &str) -> bool {
+        let before = self.paused.len();
+        self.paused.retain(|id| id != model_id);
+        if self.paused.len() == before {
+            return false;
+        }
+        self.queued.push_back(model_id.to_string());
+        true
+    }
+
+    /// Drops a download from the queue and the paused list. A running transfer stays
+    /// counted until it has stopped.
+    pub fn remove(&mut self, model_id: &str) {
+        self.queued.retain(|id| id != model_id);
+        self.paused.retain(|id| id != model_id);
+    }
+
+    pub fn is_queued(&self, model_id: &str) -> bool {
+        self.queued.iter().any(|id| id == model_id)
+    }
+
+    /// Whether the model is queued, running or paused
+    pub fn contains(&self, model_id: &str) -> bool {
+        self.is_queued(model_id)
+            || self.active.iter().any(|id| id == model_id)
+            || self.paused.iter().any(|id| id == model_id)
+    }
+
+    /// Whether a transfer for the model is running and hasn't been paused
+    pub fn is_running(&self, model_id: &str) -> bool {
+        self.active.iter().any(|id| id == model_id) && !self.paused.iter().any(|id| id == model_id)
+    }
+
+    pub fn state(&self) -> DownloadQueueState {
+        DownloadQueueState {
+            active: self
+                .active
+                .iter()
+                .filter(|id| !self.paused.contains(id))
+                .cloned()
+                .collect(),
+            queued: self.queued.iter().cloned().collect(),
+            paused: self.paused.clone(),
+        }
+    }
+}
+
+/// Keeps the combined speed of all downloads under a limit, measured over
+/// one-second windows.
+#[derive(Debug)]
+pub struct RateLimiter {
+    bytes_per_second: Option<u64>,
+    window_start: Instant,
+    used: u64,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_second: Option<u64>) -> Self {
+        RateLimiter {
+            bytes_per_second: bytes_per_second.filter(|limit| *limit > 0),
+            window_start: Instant::now(),
+            used: 0,
+        }
+    }
+
+    pub fn set_limit(&mut self, bytes_per_second: Option<u64>) {
+        *self = RateLimiter::new(bytes_per_second);
+    }
+
+    /// Records `bytes` as transferred at `now` and returns how long to wait before
+    /// transferring more.
+    pub fn consume(&mut self, bytes: u64, now: Instant) -> Duration {
+        let Some(limit) = self.bytes_per_second else {
+            return Duration::ZERO;
+        };
+
+        let mut elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.used = 0;
+            elapsed = Duration::ZERO;
+        }
+        self.used += bytes;
+
+        // How long the bytes of this window should have taken at the limit
+        let due = Duration::from_secs_f64(self.used as f64 / limit as f64);
+        due.saturating_sub(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_downloads_up_to_the_limit_in_order() {
+        let mut queue = DownloadQueue::default();
+        assert!(queue.enqueue("a"));
+        assert!(queue.enqueue("b"));
+        assert!(queue.enqueue("c"));
+        assert!(!queue.enqueue("a"));
+        assert!(queue.prioritize("c"));
+
+        assert_eq!(queue.next_to_start(2).as_deref(), Some("c"));
+        assert_eq!(queue.next_to_start(2).as_deref(), Some("a"));
+        assert_eq!(queue.next_to_start(2), None);
+        assert!(!queue.enqueue("a"));
+
+        queue.finish("c");
+        assert_eq!(queue.next_to_start(2).as_deref(), Some("b"));
+        assert_eq!(
+            queue.state(),
+            DownloadQueueState {
+                active: vec!["a".to_string(), "b".to_string()],
+                queued: vec![],
+                paused: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn paused_downloads_resume_once_stopped() {
+        let mut queue = DownloadQueue::default();
+        queue.enqueue("a");
+        queue.enqueue("b");
+        assert_eq!(queue.next_to_start(1).as_deref(), Some("a"));
+
+        assert_eq!(queue.pause("b"), PausedFrom::Queued);
+        assert_eq!(queue.pause("a"), PausedFrom::Active);
+        assert_eq!(queue.pause("a"), PausedFrom::NotQueued);
+        assert!(queue.state().active.is_empty());
+
+        // Resumed before its transfer stopped, so it has to wait
+        assert!(queue.enqueue("a"));
+        assert_eq!(queue.next_to_start(2), None);
+        queue.finish("a");
+        assert!(queue.is_queued("a"));
+        assert_eq!(queue.next_to_start(2).as_deref(), Some("a"));
+        assert_eq!(queue.state().paused, vec!["b".to_string()]);
+
+        assert!(queue.contains("b"));
+        queue.remove("b");
+        assert!(!queue.resume("b"));
+        assert!(!queue.contains("b"));
+    }
+
+    #[test]
+    fn rate_limiter_spreads_bytes_over_time() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(Some(1000));
+        limiter.window_start = start;
+
+        assert_eq!(limiter.consume(500, start), Duration::from_millis(500));
+        assert_eq!(
+            limiter.consume(500, start + Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+        // A new window starts after a second
+        assert_eq!(
+            limiter.consume(100, start + Duration::from_secs(1)),
+            Duration::from_millis(100)
+        );
+
+        let mut unlimited = RateLimiter::new(None);
+        assert_eq!(unlimited.consume(1 << 30, start), Duration::ZERO);
+    }
+}
@@ -9,7 +9,9 @@ use std::fs;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::audio_toolkit::save_wav_file;
+use crate::audio_toolkit::{save_wav_file, DetectedLanguage};
+use crate::managers::history_export::{self, HistoryExportRequest};
+use crate::managers::transcription::{TimedText, Transcription};
 
 /// Database migrations for transcription history.
 /// Each migration is applied in order. The library tracks which migrations
@@ -31,8 +33,185 @@ static MIGRATIONS: &[M] = &[
     ),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    // Segment and word timestamps, stored as JSON arrays of {start, end, text}
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN segments TEXT;
+         ALTER TABLE transcription_history ADD COLUMN words TEXT;",
+    ),
+    // Full-text index over the raw and post-processed text, kept in sync by triggers
+    M::up(
+        "CREATE VIRTUAL TABLE transcription_history_fts USING fts5(
+            transcription_text,
+            post_processed_text,
+            content='transcription_history',
+            content_rowid='id'
+        );
+        INSERT INTO transcription_history_fts(transcription_history_fts) VALUES('rebuild');
+        CREATE TRIGGER transcription_history_fts_insert AFTER INSERT ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text)
+            VALUES (new.id, new.transcription_text, new.post_processed_text);
+        END;
+        CREATE TRIGGER transcription_history_fts_delete AFTER DELETE ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(transcription_history_fts, rowid, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.transcription_text, old.post_processed_text);
+        END;
+        CREATE TRIGGER transcription_history_fts_update
+        AFTER UPDATE OF transcription_text, post_processed_text ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(transcription_history_fts, rowid, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.transcription_text, old.post_processed_text);
+            INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text)
+            VALUES (new.id, new.transcription_text, new.post_processed_text);
+        END;",
+    ),
+    // Supports keyset pagination ordered by (timestamp, id)
+    M::up(
+        "CREATE INDEX IF NOT EXISTS idx_transcription_history_timestamp_id
+         ON transcription_history (timestamp DESC, id DESC);",
+    ),
+    // Re-transcriptions of an entry; the original row is never modified
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            history_id INTEGER NOT NULL,
+            created_at INTEGER NOT NULL,
+            model_id TEXT,
+            transcription_text TEXT NOT NULL,
+            post_processed_text TEXT,
+            post_process_prompt TEXT,
+            segments TEXT,
+            words TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_transcription_revisions_history_id
+        ON transcription_revisions (history_id);
+        CREATE TRIGGER transcription_history_revisions_delete AFTER DELETE ON transcription_history BEGIN
+            DELETE FROM transcription_revisions WHERE history_id = old.id;
+        END;",
+    ),
+    // Which provider produced the post-processed text (the active one or a fallback)
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN post_process_provider TEXT;
+        ALTER TABLE transcription_revisions ADD COLUMN post_process_provider TEXT;",
+    ),
+    // Language guessed from the text in "auto" mode
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN language TEXT;
+        ALTER TABLE transcription_revisions ADD COLUMN language TEXT;",
+    ),
 ];
 
+/// Columns read by `HistoryEntry::from_row`.
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, post_process_provider, segments, words, language";
+
+/// Default and maximum page size for paginated history queries.
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+/// Default and maximum number of search results returned at once.
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const MAX_SEARCH_LIMIT: u32 = 500;
+
+/// `ENTRY_COLUMNS` qualified with a table alias, for queries that join other tables.
+fn qualified_entry_columns(alias: &str) -> String {
+    ENTRY_COLUMNS
+        .split(", ")
+        .map(|column| format!("{alias}.{column} AS {column}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Turns free-form user input into a safe FTS5 query: every word is quoted so
+/// operators and punctuation are matched literally, and the last word is
+/// prefix-matched so results update while typing.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+/// Private-use characters that `snippet()` puts around hits, so the text can be
+/// escaped before the hits are turned into `<mark>` tags.
+const HIT_START: char = '\u{E000}';
+const HIT_END: char = '\u{E001}';
+
+/// HTML-escapes a raw FTS snippet and wraps its hits in `<mark>` tags.
+fn highlight_snippet(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            HIT_START => html.push_str("<mark>"),
+            HIT_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+/// `WHERE` conditions and their bound values, shared by the history queries.
+/// Columns are qualified with `alias` for queries that join other tables.
+#[derive(Default)]
+struct EntryFilter {
+    alias: Option<&'static str>,
+    clauses: Vec<String>,
+    values: Vec<rusqlite::types::Value>,
+}
+
+impl EntryFilter {
+    fn qualified(alias: &'static str) -> Self {
+        EntryFilter {
+            alias: Some(alias),
+            ..Default::default()
+        }
+    }
+
+    fn column(&self, name: &str) -> String {
+        match self.alias {
+            Some(alias) => format!("{alias}.{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    fn push(&mut self, clause: String, values: impl IntoIterator<Item = rusqlite::types::Value>) {
+        self.clauses.push(clause);
+        self.values.extend(values);
+    }
+
+    /// Entries between the two unix timestamps (seconds), both inclusive
+    fn timestamp_range(&mut self, from: Option<i64>, to: Option<i64>) {
+        let column = self.column("timestamp");
+        if let Some(from) = from {
+            self.push(format!("{column} >= ?"), [from.into()]);
+        }
+        if let Some(to) = to {
+            self.push(format!("{column} <= ?"), [to.into()]);
+        }
+    }
+
+    fn saved_only(&mut self, saved_only: bool) {
+        if saved_only {
+            self.push(format!("{} = 1", self.column("saved")), []);
+        }
+    }
+
+    /// The `WHERE` clause, empty when nothing is filtered
+    fn sql(&self) -> String {
+        if self.clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", self.clauses.join(" AND "))
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct HistoryEntry {
     pub id: i64,
@@ -43,6 +222,137 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Provider that produced `post_processed_text`
+    pub post_process_provider: Option<String>,
+    /// Segment timestamps (seconds into the saved WAV file)
+    pub segments: Vec<TimedText>,
+    /// Word timestamps, when the engine provides them
+    pub words: Option<Vec<TimedText>>,
+    /// Language guessed from the text when transcribing in "auto" mode
+    pub language: Option<DetectedLanguage>,
+}
+
+/// A later transcription of a history entry's audio, e.g. with another model or prompt.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistoryRevision {
+    pub id: i64,
+    pub history_id: i64,
+    pub created_at: i64,
+    /// Model used for re-transcription, `None` if only post-processing was re-run
+    pub model_id: Option<String>,
+    pub transcription_text: String,
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    pub post_process_provider: Option<String>,
+    pub segments: Vec<TimedText>,
+    pub words: Option<Vec<TimedText>>,
+    pub language: Option<DetectedLanguage>,
+}
+
+impl HistoryRevision {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(HistoryRevision {
+            id: row.get("id")?,
+            history_id: row.get("history_id")?,
+            created_at: row.get("created_at")?,
+            model_id: row.get("model_id")?,
+            transcription_text: row.get("transcription_text")?,
+            post_processed_text: row.get("post_processed_text")?,
+            post_process_prompt: row.get("post_process_prompt")?,
+            post_process_provider: row.get("post_process_provider")?,
+            segments: parse_timed(row.get("segments")?).unwrap_or_default(),
+            words: parse_timed(row.get("words")?),
+            language: read_language(row)?,
+        })
+    }
+}
+
+/// Decodes a JSON timestamp column; NULL or malformed values yield `None`.
+fn parse_timed(json: Option<String>) -> Option<Vec<TimedText>> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+}
+
+fn read_language(row: &rusqlite::Row) -> rusqlite::Result<Option<DetectedLanguage>> {
+    let code: Option<String> = row.get("language")?;
+    Ok(code.map(|code| DetectedLanguage { code }))
+}
+
+/// The code stored in the `language` column.
+fn language_column(language: Option<&DetectedLanguage>) -> Option<&str> {
+    language.map(|language| language.code.as_str())
+}
+
+impl HistoryEntry {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(HistoryEntry {
+            id: row.get("id")?,
+            file_name: row.get("file_name")?,
+            timestamp: row.get("timestamp")?,
+            saved: row.get("saved")?,
+            title: row.get("title")?,
+            transcription_text: row.get("transcription_text")?,
+            post_processed_text: row.get("post_processed_text")?,
+            post_process_prompt: row.get("post_process_prompt")?,
+            post_process_provider: row.get("post_process_provider")?,
+            // Entries saved before timestamps were recorded have NULL here
+            segments: parse_timed(row.get("segments")?).unwrap_or_default(),
+            words: parse_timed(row.get("words")?),
+            language: read_language(row)?,
+        })
+    }
+}
+
+/// Position in the history list, newest first. Pass the `next_cursor` of one
+/// page to fetch the following page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HistoryCursor {
+    pub timestamp: i64,
+    pub id: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct HistoryPageQuery {
+    /// Start after this entry; `None` starts from the newest entry
+    pub cursor: Option<HistoryCursor>,
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub saved_only: bool,
+    #[serde(default)]
+    pub post_processed_only: bool,
+    /// Only entries at or after this unix timestamp (seconds)
+    pub from_timestamp: Option<i64>,
+    /// Only entries at or before this unix timestamp (seconds)
+    pub to_timestamp: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Cursor for the next page, `None` when this is the last page
+    pub next_cursor: Option<HistoryCursor>,
+}
+
+/// Optional filters applied on top of a history search.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct HistorySearchFilters {
+    /// Only entries at or after this unix timestamp (seconds)
+    pub from_timestamp: Option<i64>,
+    /// Only entries at or before this unix timestamp (seconds)
+    pub to_timestamp: Option<i64>,
+    #[serde(default)]
+    pub saved_only: bool,
+    /// Only entries post-processed with exactly this prompt
+    pub post_process_prompt: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistorySearchResult {
+    pub entry: HistoryEntry,
+    /// Matching text, HTML-escaped, with hits wrapped in `<mark>` tags
+    pub snippet: String,
+    /// BM25 relevance, lower is better
+    pub rank: f64,
 }
 
 pub struct HistoryManager {
@@ -180,9 +490,10 @@ impl HistoryManager {
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
-        transcription_text: String,
+        transcription: Transcription,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        post_process_provider: Option<String>,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("handy-{}.wav", timestamp);
@@ -197,9 +508,10 @@ impl HistoryManager {
             file_name,
             timestamp,
             title,
-            transcription_text,
+            transcription,
             post_processed_text,
             post_process_prompt,
+            post_process_provider,
         )?;
 
         // Clean up old entries
@@ -213,19 +525,28 @@ impl HistoryManager {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn save_to_database(
         &self,
         file_name: String,
         timestamp: i64,
         title: String,
-        transcription_text: String,
+        transcription: Transcription,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        post_process_provider: Option<String>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
+        let segments = serde_json::to_string(&transcription.segments)?;
+        let words = transcription
+            .words
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let language = language_column(transcription.language.as_ref());
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, post_process_provider, segments, words, language) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![file_name, timestamp, false, title, transcription.text, post_processed_text, post_process_prompt, post_process_provider, segments, words, language],
         )?;
 
         debug!("Saved transcription to database");
@@ -354,22 +675,12 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            ENTRY_COLUMNS
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-            })
-        })?;
+        let rows = stmt.query_map([], HistoryEntry::from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -379,33 +690,88 @@ impl HistoryManager {
         Ok(entries)
     }
 
+    pub async fn get_history_page(&self, query: &HistoryPageQuery) -> Result<HistoryPage> {
+        let conn = self.get_connection()?;
+        Self::get_history_page_with_conn(&conn, query)
+    }
+
+    fn get_history_page_with_conn(
+        conn: &Connection,
+        query: &HistoryPageQuery,
+    ) -> Result<HistoryPage> {
+        let mut filter = EntryFilter::default();
+
+        if let Some(cursor) = query.cursor {
+            filter.push(
+                "(timestamp < ? OR (timestamp = ? AND id < ?))".to_string(),
+                [
+                    cursor.timestamp.into(),
+                    cursor.timestamp.into(),
+                    cursor.id.into(),
+                ],
+            );
+        }
+        filter.saved_only(query.saved_only);
+        if query.post_processed_only {
+            filter.push("post_processed_text IS NOT NULL".to_string(), []);
+        }
+        filter.timestamp_range(query.from_timestamp, query.to_timestamp);
+
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE) as usize;
+        let where_clause = filter.sql();
+        let mut values = filter.values;
+        // Fetch one extra row to know whether another page follows
+        values.push((limit as i64 + 1).into());
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history {} ORDER BY timestamp DESC, id DESC LIMIT ?",
+            ENTRY_COLUMNS, where_clause
+        ))?;
+
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(values.iter()),
+            HistoryEntry::from_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        let next_cursor = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|entry| HistoryCursor {
+                timestamp: entry.timestamp,
+                id: entry.id,
+            })
+        } else {
+            None
+        };
+
+        Ok(HistoryPage {
+            entries,
+            next_cursor,
+        })
+    }
+
     pub fn get_latest_entry(&self) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         Self::get_latest_entry_with_conn(&conn)
     }
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM transcription_history
              ORDER BY timestamp DESC
              LIMIT 1",
-        )?;
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([], HistoryEntry::from_row).optional()?;
 
         Ok(entry)
     }
@@ -443,27 +809,94 @@ impl HistoryManager {
 
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            ENTRY_COLUMNS
+        ))?;
+
+        let entry = stmt.query_row([id], HistoryEntry::from_row).optional()?;
+
+        Ok(entry)
+    }
+
+    /// Store a re-transcription of an existing entry. The entry itself is left untouched.
+    pub fn add_revision(
+        &self,
+        history_id: i64,
+        model_id: Option<String>,
+        transcription: Transcription,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+        post_process_provider: Option<String>,
+    ) -> Result<HistoryRevision> {
+        let conn = self.get_connection()?;
+        let revision = Self::add_revision_with_conn(
+            &conn,
+            history_id,
+            model_id,
+            transcription,
+            post_processed_text,
+            post_process_prompt,
+            post_process_provider,
+        )?;
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(revision)
+    }
+
+    fn add_revision_with_conn(
+        conn: &Connection,
+        history_id: i64,
+        model_id: Option<String>,
+        transcription: Transcription,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+        post_process_provider: Option<String>,
+    ) -> Result<HistoryRevision> {
+        let segments = serde_json::to_string(&transcription.segments)?;
+        let words = transcription
+            .words
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let language = language_column(transcription.language.as_ref());
+
+        conn.execute(
+            "INSERT INTO transcription_revisions (history_id, created_at, model_id, transcription_text, post_processed_text, post_process_prompt, post_process_provider, segments, words, language) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![history_id, Utc::now().timestamp(), model_id, transcription.text, post_processed_text, post_process_prompt, post_process_provider, segments, words, language],
+        )?;
+        let id = conn.last_insert_rowid();
+
+        debug!("Saved revision {} for history entry {}", id, history_id);
+        Ok(conn.query_row(
+            "SELECT * FROM transcription_revisions WHERE id = ?1",
+            params![id],
+            HistoryRevision::from_row,
+        )?)
+    }
+
+    /// All revisions of an entry, oldest first.
+    pub async fn get_revisions(&self, history_id: i64) -> Result<Vec<HistoryRevision>> {
+        let conn = self.get_connection()?;
+        Self::get_revisions_with_conn(&conn, history_id)
+    }
+
+    fn get_revisions_with_conn(conn: &Connection, history_id: i64) -> Result<Vec<HistoryRevision>> {
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
+            "SELECT * FROM transcription_revisions WHERE history_id = ?1 ORDER BY created_at ASC, id ASC",
         )?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let rows = stmt.query_map(params![history_id], HistoryRevision::from_row)?;
 
-        Ok(entry)
+        let mut revisions = Vec::new();
+        for row in rows {
+            revisions.push(row?);
+        }
+
+        Ok(revisions)
     }
 
     pub async fn delete_entry(&self, id: i64) -> Result<()> {
@@ -497,6 +930,124 @@ impl HistoryManager {
         Ok(())
     }
 
+    pub async fn search_history(
+        &self,
+        query: &str,
+        filters: &HistorySearchFilters,
+    ) -> Result<Vec<HistorySearchResult>> {
+        let conn = self.get_connection()?;
+        Self::search_with_conn(&conn, query, filters)
+    }
+
+    fn search_with_conn(
+        conn: &Connection,
+        query: &str,
+        filters: &HistorySearchFilters,
+    ) -> Result<Vec<HistorySearchResult>> {
+        let Some(match_expr) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut filter = EntryFilter::qualified("h");
+        filter.push(
+            "transcription_history_fts MATCH ?".to_string(),
+            [match_expr.into()],
+        );
+        filter.timestamp_range(filters.from_timestamp, filters.to_timestamp);
+        filter.saved_only(filters.saved_only);
+        if let Some(prompt) = &filters.post_process_prompt {
+            let column = filter.column("post_process_prompt");
+            filter.push(format!("{column} = ?"), [prompt.clone().into()]);
+        }
+        let limit = filters
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+        let where_clause = filter.sql();
+        let mut values = filter.values;
+        values.push(i64::from(limit).into());
+
+        // Column -1 lets snippet() pick whichever column matched best
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {},
+                    snippet(transcription_history_fts, -1, '{HIT_START}', '{HIT_END}', '…', 16) AS snippet,
+                    bm25(transcription_history_fts) AS score
+             FROM transcription_history_fts
+             JOIN transcription_history h ON h.id = transcription_history_fts.rowid
+             {}
+             ORDER BY score, h.timestamp DESC
+             LIMIT ?",
+            qualified_entry_columns("h"),
+            where_clause
+        ))?;
+
+        let rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), |row| {
+            Ok(HistorySearchResult {
+                entry: HistoryEntry::from_row(row)?,
+                snippet: highlight_snippet(&row.get::<_, String>("snippet")?),
+                rank: row.get("score")?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Export the entries selected by `request`. Returns the number of entries written.
+    pub async fn export_entries(&self, request: &HistoryExportRequest) -> Result<usize> {
+        let conn = self.get_connection()?;
+        let entries = Self::get_entries_for_export_with_conn(&conn, request)?;
+        if entries.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No history entries match the export selection"
+            ));
+        }
+
+        history_export::write_export(&entries, &self.recordings_dir, request)
+    }
+
+    /// Entries selected by id, or by timestamp range when no ids are given, oldest first.
+    fn get_entries_for_export_with_conn(
+        conn: &Connection,
+        request: &HistoryExportRequest,
+    ) -> Result<Vec<HistoryEntry>> {
+        let mut filter = EntryFilter::default();
+
+        if let Some(ids) = &request.ids {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            filter.push(
+                format!("id IN ({})", vec!["?"; ids.len()].join(", ")),
+                ids.iter().map(|id| (*id).into()),
+            );
+        } else {
+            filter.timestamp_range(request.from_timestamp, request.to_timestamp);
+        }
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history {} ORDER BY timestamp ASC, id ASC",
+            ENTRY_COLUMNS,
+            filter.sql()
+        ))?;
+
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(filter.values.iter()),
+            HistoryEntry::from_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(entries)
+    }
+
     fn format_timestamp_title(&self, timestamp: i64) -> String {
         if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
             // Convert UTC to local timezone
@@ -514,20 +1065,10 @@ mod tests {
     use rusqlite::{params, Connection};
 
     fn setup_conn() -> Connection {
-        let conn = Connection::open_in_memory().expect("open in-memory db");
-        conn.execute_batch(
-            "CREATE TABLE transcription_history (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                file_name TEXT NOT NULL,
-                timestamp INTEGER NOT NULL,
-                saved BOOLEAN NOT NULL DEFAULT 0,
-                title TEXT NOT NULL,
-                transcription_text TEXT NOT NULL,
-                post_processed_text TEXT,
-                post_process_prompt TEXT
-            );",
-        )
-        .expect("create transcription_history table");
+        let mut conn = Connection::open_in_memory().expect("open in-memory db");
+        Migrations::new(MIGRATIONS.to_vec())
+            .to_latest(&mut conn)
+            .expect("apply migrations");
         conn
     }
 
@@ -568,5 +1109,330 @@ mod tests {
         assert_eq!(entry.timestamp, 200);
         assert_eq!(entry.transcription_text, "second");
         assert_eq!(entry.post_processed_text.as_deref(), Some("processed"));
+        assert!(entry.segments.is_empty());
+        assert!(entry.words.is_none());
+    }
+
+    #[test]
+    fn entry_round_trips_timestamps() {
+        let conn = setup_conn();
+        let segments = vec![TimedText {
+            start: 0.0,
+            end: 1.5,
+            text: "hello world".to_string(),
+        }];
+        let words = vec![
+            TimedText {
+                start: 0.0,
+                end: 0.6,
+                text: "hello".to_string(),
+            },
+            TimedText {
+                start: 0.7,
+                end: 1.5,
+                text: "world".to_string(),
+            },
+        ];
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, segments, words)
+             VALUES ('handy-300.wav', 300, 0, 'Recording 300', 'hello world', ?1, ?2)",
+            params![
+                serde_json::to_string(&segments).unwrap(),
+                serde_json::to_string(&words).unwrap()
+            ],
+        )
+        .expect("insert history entry");
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+
+        assert_eq!(entry.segments, segments);
+        assert_eq!(entry.words, Some(words));
+    }
+
+    #[test]
+    fn revisions_keep_original_and_are_deleted_with_entry() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "original text", None);
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .unwrap()
+            .unwrap();
+
+        let revision = HistoryManager::add_revision_with_conn(
+            &conn,
+            entry.id,
+            Some("parakeet-tdt-0.6b-v3".to_string()),
+            Transcription {
+                text: "better text".to_string(),
+                ..Default::default()
+            },
+            Some("Better text.".to_string()),
+            Some("Fix punctuation: ${output}".to_string()),
+            Some("openai".to_string()),
+        )
+        .expect("add revision");
+        assert_eq!(revision.history_id, entry.id);
+        assert_eq!(revision.model_id.as_deref(), Some("parakeet-tdt-0.6b-v3"));
+
+        let latest = HistoryManager::get_latest_entry_with_conn(&conn)
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.transcription_text, "original text");
+
+        let revisions = HistoryManager::get_revisions_with_conn(&conn, entry.id).unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(
+            revisions[0].post_processed_text.as_deref(),
+            Some("Better text.")
+        );
+        assert_eq!(
+            revisions[0].post_process_provider.as_deref(),
+            Some("openai")
+        );
+
+        conn.execute(
+            "DELETE FROM transcription_history WHERE id = ?1",
+            params![entry.id],
+        )
+        .unwrap();
+        assert!(HistoryManager::get_revisions_with_conn(&conn, entry.id)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn stores_detected_language() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "hallo welt", None);
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .unwrap()
+            .unwrap();
+        assert!(entry.language.is_none());
+
+        let language = DetectedLanguage {
+            code: "de".to_string(),
+        };
+        let revision = HistoryManager::add_revision_with_conn(
+            &conn,
+            entry.id,
+            None,
+            Transcription {
+                text: "Hallo Welt".to_string(),
+                language: Some(language.clone()),
+                ..Default::default()
+            },
+            None,
+            None,
+            None,
+        )
+        .expect("add revision");
+        assert_eq!(revision.language, Some(language));
+    }
+
+    #[test]
+    fn history_page_walks_all_entries() {
+        let conn = setup_conn();
+        // Two entries share a timestamp so the id tie-breaker is exercised
+        for (timestamp, text) in [(100, "a"), (200, "b"), (200, "c"), (300, "d"), (400, "e")] {
+            insert_entry(&conn, timestamp, text, None);
+        }
+
+        let mut query = HistoryPageQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+        loop {
+            let page = HistoryManager::get_history_page_with_conn(&conn, &query).expect("page");
+            assert!(page.entries.len() <= 2);
+            seen.extend(page.entries.into_iter().map(|e| e.transcription_text));
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen, vec!["e", "d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn history_page_filters() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "plain", None);
+        insert_entry(&conn, 200, "processed", Some("Processed."));
+        insert_entry(&conn, 300, "starred", None);
+        conn.execute(
+            "UPDATE transcription_history SET saved = 1 WHERE timestamp = 300",
+            [],
+        )
+        .unwrap();
+
+        let texts = |query: HistoryPageQuery| -> Vec<String> {
+            HistoryManager::get_history_page_with_conn(&conn, &query)
+                .expect("page")
+                .entries
+                .into_iter()
+                .map(|e| e.transcription_text)
+                .collect()
+        };
+
+        assert_eq!(
+            texts(HistoryPageQuery {
+                saved_only: true,
+                ..Default::default()
+            }),
+            vec!["starred"]
+        );
+        assert_eq!(
+            texts(HistoryPageQuery {
+                post_processed_only: true,
+                ..Default::default()
+            }),
+            vec!["processed"]
+        );
+        assert_eq!(
+            texts(HistoryPageQuery {
+                from_timestamp: Some(150),
+                to_timestamp: Some(250),
+                ..Default::default()
+            }),
+            vec!["processed"]
+        );
+    }
+
+    #[test]
+    fn fts_query_quotes_terms() {
+        assert_eq!(fts_query("   "), None);
+        assert_eq!(fts_query("hello"), Some("\"hello\"*".to_string()));
+        assert_eq!(
+            fts_query("meeting AND \"notes"),
+            Some("\"meeting\" \"AND\" \"\"\"notes\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn search_matches_raw_and_post_processed_text() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "buy milk tomorrow", None);
+        insert_entry(
+            &conn,
+            200,
+            "call the dentist",
+            Some("Call the dentist about the appointment."),
+        );
+        insert_entry(&conn, 300, "unrelated note", None);
+
+        let results =
+            HistoryManager::search_with_conn(&conn, "appoint", &HistorySearchFilters::default())
+                .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.timestamp, 200);
+        assert!(results[0].snippet.contains("<mark>appointment</mark>"));
+
+        let results =
+            HistoryManager::search_with_conn(&conn, "milk", &HistorySearchFilters::default())
+                .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.transcription_text, "buy milk tomorrow");
+    }
+
+    #[test]
+    fn search_snippet_escapes_html() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "say <script>alert</script> & bye", None);
+
+        let results =
+            HistoryManager::search_with_conn(&conn, "alert", &HistorySearchFilters::default())
+                .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].snippet,
+            "say &lt;script&gt;<mark>alert</mark>&lt;/script&gt; &amp; bye"
+        );
+    }
+
+    #[test]
+    fn search_applies_filters_and_tracks_deletes() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "project update one", None);
+        insert_entry(&conn, 200, "project update two", None);
+        conn.execute(
+            "UPDATE transcription_history SET saved = 1 WHERE timestamp = 200",
+            [],
+        )
+        .unwrap();
+
+        let saved_only = HistorySearchFilters {
+            saved_only: true,
+            ..Default::default()
+        };
+        let results = HistoryManager::search_with_conn(&conn, "project", &saved_only).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.timestamp, 200);
+
+        let early = HistorySearchFilters {
+            to_timestamp: Some(150),
+            ..Default::default()
+        };
+        let results = HistoryManager::search_with_conn(&conn, "project", &early).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.timestamp, 100);
+
+        conn.execute(
+            "DELETE FROM transcription_history WHERE timestamp = 100",
+            [],
+        )
+        .unwrap();
+        let results =
+            HistoryManager::search_with_conn(&conn, "project", &HistorySearchFilters::default())
+                .unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    fn export_request(
+        ids: Option<Vec<i64>>,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> HistoryExportRequest {
+        HistoryExportRequest {
+            format: history_export::ExportFormat::Jsonl,
+            ids,
+            from_timestamp: from,
+            to_timestamp: to,
+            destination: String::new(),
+            archive: None,
+            post_processed: true,
+        }
+    }
+
+    #[test]
+    fn export_selection_by_range_and_ids() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "first", None);
+        insert_entry(&conn, 200, "second", None);
+        insert_entry(&conn, 300, "third", None);
+
+        let in_range = HistoryManager::get_entries_for_export_with_conn(
+            &conn,
+            &export_request(None, Some(150), Some(300)),
+        )
+        .expect("select by range");
+        let texts: Vec<&str> = in_range
+            .iter()
+            .map(|e| e.transcription_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["second", "third"]);
+
+        let by_id = HistoryManager::get_entries_for_export_with_conn(
+            &conn,
+            &export_request(Some(vec![in_range[1].id, 1]), None, None),
+        )
+        .expect("select by id");
+        let texts: Vec<&str> = by_id
+            .iter()
+            .map(|e| e.transcription_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["first", "third"]);
     }
 }
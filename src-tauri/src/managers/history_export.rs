@@ -0,0 +1,464 @@
+//! Export of transcription history to subtitle, JSON Lines and Markdown formats,
+//! optionally bundled with the recorded WAV files into a zip or tar.gz archive.
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::managers::history::HistoryEntry;
+use crate::managers::transcription::TimedText;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Jsonl,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+/// Which entries to export and where to write them.
+///
+/// Entries are selected by `ids` when given, otherwise by the optional
+/// `[from_timestamp, to_timestamp]` range (unix seconds, inclusive).
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct HistoryExportRequest {
+    pub format: ExportFormat,
+    pub ids: Option<Vec<i64>>,
+    pub from_timestamp: Option<i64>,
+    pub to_timestamp: Option<i64>,
+    /// Output file. With `archive` set this is the archive path.
+    pub destination: String,
+    /// Bundle the export together with the referenced WAV files.
+    pub archive: Option<ArchiveFormat>,
+    /// Export the post-processed text of entries that have one instead of the
+    /// original transcription. Subtitle cues for those entries then span the whole
+    /// recording, as the post-processed text has no timestamps of its own.
+    #[serde(default = "crate::utils::default_true")]
+    pub post_processed: bool,
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+/// Writes the export described by `request` and returns the number of entries exported.
+pub fn write_export(
+    entries: &[HistoryEntry],
+    recordings_dir: &Path,
+    request: &HistoryExportRequest,
+) -> Result<usize> {
+    let destination = PathBuf::from(&request.destination);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match request.archive {
+        None => {
+            let durations = entry_durations(entries, recordings_dir);
+            let content = render(entries, &durations, request)?;
+            fs::write(&destination, content)?;
+        }
+        Some(archive) => {
+            let files = archive_files(entries, recordings_dir, request)?;
+            match archive {
+                ArchiveFormat::Zip => write_zip(&destination, &files)?,
+                ArchiveFormat::TarGz => write_tar_gz(&destination, &files)?,
+            }
+        }
+    }
+
+    debug!(
+        "Exported {} history entries as {:?} to {:?}",
+        entries.len(),
+        request.format,
+        destination
+    );
+    Ok(entries.len())
+}
+
+enum ArchiveSource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+/// Lists the files that go into an archive. Subtitles are written per recording
+/// (`handy-123.srt` next to `handy-123.wav`) so media players pick them up;
+/// JSON Lines and Markdown exports are a single document.
+fn archive_files(
+    entries: &[HistoryEntry],
+    recordings_dir: &Path,
+    request: &HistoryExportRequest,
+) -> Result<Vec<(String, ArchiveSource)>> {
+    let format = request.format;
+    let mut files = Vec::new();
+
+    match format {
+        ExportFormat::Srt | ExportFormat::Vtt => {
+            for entry in entries {
+                let duration = wav_duration(&recordings_dir.join(&entry.file_name));
+                let content = render(
+                    std::slice::from_ref(entry),
+                    &[duration.unwrap_or_default()],
+                    request,
+                )?;
+                let stem = Path::new(&entry.file_name)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| entry.id.to_string());
+                files.push((
+                    format!("recordings/{}.{}", stem, format.extension()),
+                    ArchiveSource::Bytes(content.into_bytes()),
+                ));
+            }
+        }
+        ExportFormat::Jsonl | ExportFormat::Markdown => {
+            let content = render(entries, &[], request)?;
+            files.push((
+                format!("transcriptions.{}", format.extension()),
+                ArchiveSource::Bytes(content.into_bytes()),
+            ));
+        }
+    }
+
+    for entry in entries {
+        let path = recordings_dir.join(&entry.file_name);
+        if path.exists() {
+            files.push((
+                format!("recordings/{}", entry.file_name),
+                ArchiveSource::File(path),
+            ));
+        } else {
+            warn!("Recording {} is missing, skipping", entry.file_name);
+        }
+    }
+
+    Ok(files)
+}
+
+fn write_zip(destination: &Path, files: &[(String, ArchiveSource)]) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(File::create(destination)?);
+    let options = zip::write::SimpleFileOptions::default();
+
+    for (name, source) in files {
+        zip.start_file(name.as_str(), options)?;
+        match source {
+            ArchiveSource::Bytes(bytes) => zip.write_all(bytes)?,
+            ArchiveSource::File(path) => {
+                std::io::copy(&mut File::open(path)?, &mut zip)?;
+            }
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn write_tar_gz(destination: &Path, files: &[(String, ArchiveSource)]) -> Result<()> {
+    let encoder = GzEncoder::new(File::create(destination)?, Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+
+    for (name, source) in files {
+        match source {
+            ArchiveSource::Bytes(bytes) => {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(bytes.len() as u64);
+                header.set_mode(0o644);
+                header.set_mtime(chrono::Utc::now().timestamp() as u64);
+                header.set_cksum();
+                tar.append_data(&mut header, name, bytes.as_slice())?;
+            }
+            ArchiveSource::File(path) => tar.append_path_with_name(path, name)?,
+        }
+    }
+
+    tar.into_inner()?.finish()?;
+    Ok(())
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+fn wav_duration(path: &Path) -> Option<f32> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let spec = reader.spec();
+    Some(reader.duration() as f32 / spec.sample_rate as f32)
+}
+
+/// Recording lengths used to lay entries out on one subtitle timeline. Falls back
+/// to the last segment end when the WAV file is gone.
+fn entry_durations(entries: &[HistoryEntry], recordings_dir: &Path) -> Vec<f32> {
+    entries
+        .iter()
+        .map(|entry| {
+            wav_duration(&recordings_dir.join(&entry.file_name))
+                .or_else(|| entry.segments.last().map(|s| s.end))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The post-processed text of an entry, if it has one and it was asked for
+fn post_processed_text(entry: &HistoryEntry, post_processed: bool) -> Option<&str> {
+    entry
+        .post_processed_text
+        .as_deref()
+        .filter(|_| post_processed)
+}
+
+/// The text exported for an entry
+fn display_text(entry: &HistoryEntry, post_processed: bool) -> &str {
+    post_processed_text(entry, post_processed).unwrap_or(&entry.transcription_text)
+}
+
+/// Subtitle cues for one entry. Post-processed entries and entries saved before
+/// timestamps were recorded become a single cue spanning the recording.
+fn cues(entry: &HistoryEntry, duration: f32, post_processed: bool) -> Vec<TimedText> {
+    let processed = post_processed_text(entry, post_processed);
+    if processed.is_none() && !entry.segments.is_empty() {
+        return entry.segments.clone();
+    }
+    vec![TimedText {
+        start: entry.segments.first().map_or(0.0, |s| s.start),
+        end: entry.segments.last().map_or(duration, |s| s.end).max(1.0),
+        text: display_text(entry, post_processed).to_string(),
+    }]
+}
+
+fn render(
+    entries: &[HistoryEntry],
+    durations: &[f32],
+    request: &HistoryExportRequest,
+) -> Result<String> {
+    let post_processed = request.post_processed;
+    Ok(match request.format {
+        ExportFormat::Srt => render_subtitles(entries, durations, false, post_processed),
+        ExportFormat::Vtt => render_subtitles(entries, durations, true, post_processed),
+        ExportFormat::Jsonl => render_jsonl(entries)?,
+        ExportFormat::Markdown => render_markdown(entries, post_processed),
+    })
+}
+
+/// Renders SRT or WebVTT. Multiple entries are placed back to back, each offset
+/// by the total length of the recordings before it.
+fn render_subtitles(
+    entries: &[HistoryEntry],
+    durations: &[f32],
+    vtt: bool,
+    post_processed: bool,
+) -> String {
+    let mut out = String::new();
+    if vtt {
+        out.push_str("WEBVTT\n\n");
+    }
+
+    let mut offset = 0.0;
+    let mut index = 1;
+    for (i, entry) in entries.iter().enumerate() {
+        let duration = durations.get(i).copied().unwrap_or_default();
+        for cue in cues(entry, duration, post_processed) {
+            if !vtt {
+                out.push_str(&format!("{}\n", index));
+            }
+            out.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(offset + cue.start, vtt),
+                format_timestamp(offset + cue.end, vtt),
+                cue.text.trim()
+            ));
+            index += 1;
+        }
+        offset += duration.max(entry.segments.last().map(|s| s.end).unwrap_or(0.0));
+    }
+
+    out
+}
+
+/// `HH:MM:SS,mmm` for SRT, `HH:MM:SS.mmm` for WebVTT.
+fn format_timestamp(seconds: f32, vtt: bool) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hours, rem) = (total_ms / 3_600_000, total_ms % 3_600_000);
+    let (minutes, rem) = (rem / 60_000, rem % 60_000);
+    let (secs, ms) = (rem / 1000, rem % 1000);
+    let sep = if vtt { '.' } else { ',' };
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, secs, sep, ms)
+}
+
+fn render_jsonl(entries: &[HistoryEntry]) -> Result<String> {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&serde_json::to_string(entry)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn render_markdown(entries: &[HistoryEntry], post_processed: bool) -> String {
+    let mut out = String::from("# Handy Transcriptions\n");
+
+    for entry in entries {
+        let when = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| {
+                dt.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        out.push_str(&format!("\n## {}\n\n", entry.title));
+        out.push_str(&format!("*{}* · `{}`", when, entry.file_name));
+        if entry.saved {
+            out.push_str(" · ★");
+        }
+        out.push_str("\n\n");
+        out.push_str(display_text(entry, post_processed).trim());
+        out.push('\n');
+
+        if post_processed_text(entry, post_processed).is_some() {
+            out.push_str("\n<details><summary>Original transcription</summary>\n\n");
+            out.push_str(entry.transcription_text.trim());
+            out.push_str("\n\n</details>\n");
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, segments: Vec<TimedText>) -> HistoryEntry {
+        HistoryEntry {
+            id: 1,
+            file_name: "handy-100.wav".to_string(),
+            timestamp: 100,
+            saved: false,
+            title: "Recording".to_string(),
+            transcription_text: text.to_string(),
+            post_processed_text: None,
+            post_process_prompt: None,
+            post_process_provider: None,
+            segments,
+            words: None,
+            language: None,
+        }
+    }
+
+    fn span(start: f32, end: f32, text: &str) -> TimedText {
+        TimedText {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0, false), "00:00:00,000");
+        assert_eq!(format_timestamp(3661.5, false), "01:01:01,500");
+        assert_eq!(format_timestamp(1.25, true), "00:00:01.250");
+    }
+
+    #[test]
+    fn test_render_srt_uses_segments() {
+        let entries = vec![entry(
+            "Hello there. How are you?",
+            vec![
+                span(0.0, 1.2, "Hello there."),
+                span(1.4, 2.8, "How are you?"),
+            ],
+        )];
+        let srt = render_subtitles(&entries, &[3.0], false, true);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,200\nHello there.\n\n\
+             2\n00:00:01,400 --> 00:00:02,800\nHow are you?\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_vtt_offsets_entries() {
+        let entries = vec![
+            entry("first", vec![span(0.0, 1.0, "first")]),
+            entry("second", vec![span(0.5, 1.5, "second")]),
+        ];
+        let vtt = render_subtitles(&entries, &[2.0, 2.0], true, true);
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:02.500 --> 00:00:03.500\nsecond"));
+    }
+
+    #[test]
+    fn test_render_subtitles_without_segments() {
+        let entries = vec![entry("legacy entry", vec![])];
+        let srt = render_subtitles(&entries, &[4.0], false, true);
+        assert_eq!(srt, "1\n00:00:00,000 --> 00:00:04,000\nlegacy entry\n\n");
+    }
+
+    #[test]
+    fn test_render_jsonl_one_line_per_entry() {
+        let entries = vec![entry("a", vec![]), entry("b", vec![])];
+        let jsonl = render_jsonl(&entries).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: HistoryEntry = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed.transcription_text, "b");
+    }
+
+    #[test]
+    fn test_render_markdown_prefers_post_processed_text() {
+        let mut e = entry("raw text", vec![]);
+        e.post_processed_text = Some("Clean text.".to_string());
+        let md = render_markdown(std::slice::from_ref(&e), true);
+        assert!(md.contains("## Recording"));
+        assert!(md.contains("\n\nClean text.\n"));
+        assert!(md.contains("Original transcription"));
+
+        let md = render_markdown(&[e], false);
+        assert!(md.contains("\n\nraw text\n"));
+        assert!(!md.contains("Clean text."));
+    }
+
+    #[test]
+    fn test_render_subtitles_with_post_processed_text() {
+        let mut e = entry(
+            "um hello there how are you",
+            vec![
+                span(0.5, 1.2, "um hello there"),
+                span(1.4, 2.8, "how are you"),
+            ],
+        );
+        e.post_processed_text = Some("Hello there, how are you?".to_string());
+        let entries = vec![e];
+
+        let srt = render_subtitles(&entries, &[3.0], false, true);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,500 --> 00:00:02,800\nHello there, how are you?\n\n"
+        );
+        let srt = render_subtitles(&entries, &[3.0], false, false);
+        assert!(srt.contains("00:00:01,400 --> 00:00:02,800\nhow are you"));
+    }
+}
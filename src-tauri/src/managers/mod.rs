@@ -1,5 +1,9 @@
 pub mod audio;
+pub mod download_queue;
 pub mod history;
+pub mod history_export;
 pub mod model;
+pub mod model_catalog;
+pub mod model_checksum;
 pub mod transcription;
 pub mod foundry;
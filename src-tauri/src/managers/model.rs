@@ -1,20 +1,30 @@
+use crate::managers::download_queue::{DownloadQueue, DownloadQueueState, PausedFrom, RateLimiter};
+use crate::managers::model_catalog::{
+    add_user_model, bundled_manifest, merge_manifests, mirrored_url, parse_manifest,
+    pin_bundled_sources, remove_user_model, ManifestModel, ModelManifest,
+    REMOTE_CATALOG_CACHE_FILE, USER_MANIFEST_FILE,
+};
+use crate::managers::model_checksum::{
+    checksum_path, digests_match, model_digest, read_recorded_digest, sha256_file, ChecksumMismatch,
+};
 use crate::settings::{get_settings, write_settings};
 use anyhow::Result;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
-use log::{debug, info, warn};
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum EngineType {
@@ -30,6 +40,7 @@ pub struct ModelInfo {
     pub description: String,
     pub filename: String,
     pub url: Option<String>,
+    pub sha256: Option<String>, // Expected SHA-256 of the download, if the catalog lists one
     pub size_mb: u64,
     pub is_downloaded: bool,
     pub is_downloading: bool,
@@ -43,6 +54,33 @@ pub struct ModelInfo {
     pub supported_languages: Vec<String>, // Languages this model can transcribe
 }
 
+/// How an imported model is placed in the models directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    #[default]
+    Copy,
+    /// Link to the original so large models aren't stored twice
+    Symlink,
+}
+
+/// A local model file or directory to add to the catalog
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelImportRequest {
+    pub path: String,
+    /// Defaults to the file or directory name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Inferred from the file layout when not given
+    #[serde(default)]
+    pub engine_type: Option<EngineType>,
+    #[serde(default)]
+    pub mode: ImportMode,
+    /// Defaults to every Whisper language for Whisper models and English otherwise
+    #[serde(default)]
+    pub supported_languages: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct DownloadProgress {
     pub model_id: String,
@@ -53,239 +91,44 @@ pub struct DownloadProgress {
 
 pub struct ModelManager {
     app_handle: AppHandle,
+    app_data_dir: PathBuf,
     models_dir: PathBuf,
     available_models: Mutex<HashMap<String, ModelInfo>>,
     cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     extracting_models: Arc<Mutex<HashSet<String>>>,
+    download_queue: Mutex<DownloadQueue>,
+    /// Callers of `download_model` waiting for a model's download to end
+    download_waiters: Mutex<HashMap<String, Vec<oneshot::Sender<Result<(), String>>>>>,
+    rate_limiter: Mutex<RateLimiter>,
 }
 
 impl ModelManager {
     pub fn new(app_handle: &AppHandle) -> Result<Self> {
         // Create models directory in app data
-        let models_dir = app_handle
+        let app_data_dir = app_handle
             .path()
             .app_data_dir()
-            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
-            .join("models");
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?;
+        let models_dir = app_data_dir.join("models");
 
         if !models_dir.exists() {
             fs::create_dir_all(&models_dir)?;
         }
 
-        let mut available_models = HashMap::new();
-
-        // Whisper supported languages (99 languages from tokenizer)
-        // Including zh-Hans and zh-Hant variants to match frontend language codes
-        let whisper_languages: Vec<String> = vec![
-            "en", "zh", "zh-Hans", "zh-Hant", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl",
-            "ca", "nl", "ar", "sv", "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs",
-            "ro", "da", "hu", "ta", "no", "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy",
-            "sk", "te", "fa", "lv", "bn", "sr", "az", "sl", "kn", "et", "mk", "br", "eu", "is",
-            "hy", "ne", "mn", "bs", "kk", "sq", "sw", "gl", "mr", "pa", "si", "km", "sn", "yo",
-            "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am", "yi", "lo", "uz", "fo", "ht",
-            "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as", "tt", "haw", "ln",
-            "ha", "ba", "jw", "su", "yue",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        // TODO this should be read from a JSON file or something..
-        available_models.insert(
-            "small".to_string(),
-            ModelInfo {
-                id: "small".to_string(),
-                name: "Whisper Small".to_string(),
-                description: "Fast and fairly accurate.".to_string(),
-                filename: "ggml-small.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
-                size_mb: 487,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.60,
-                speed_score: 0.85,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-            },
-        );
-
-        // Add downloadable models
-        available_models.insert(
-            "medium".to_string(),
-            ModelInfo {
-                id: "medium".to_string(),
-                name: "Whisper Medium".to_string(),
-                description: "Good accuracy, medium speed".to_string(),
-                filename: "whisper-medium-q4_1.bin".to_string(),
-                url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
-                size_mb: 492, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.75,
-                speed_score: 0.60,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-            },
-        );
-
-        available_models.insert(
-            "turbo".to_string(),
-            ModelInfo {
-                id: "turbo".to_string(),
-                name: "Whisper Turbo".to_string(),
-                description: "Balanced accuracy and speed.".to_string(),
-                filename: "ggml-large-v3-turbo.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
-                size_mb: 1600, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.80,
-                speed_score: 0.40,
-                supports_translation: false, // Turbo doesn't support translation
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-            },
-        );
-
-        available_models.insert(
-            "large".to_string(),
-            ModelInfo {
-                id: "large".to_string(),
-                name: "Whisper Large".to_string(),
-                description: "Good accuracy, but slow.".to_string(),
-                filename: "ggml-large-v3-q5_0.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
-                size_mb: 1100, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.30,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-            },
-        );
-
-        available_models.insert(
-            "breeze-asr".to_string(),
-            ModelInfo {
-                id: "breeze-asr".to_string(),
-                name: "Breeze ASR".to_string(),
-                description: "Optimized for Taiwanese Mandarin. Code-switching support."
-                    .to_string(),
-                filename: "breeze-asr-q5_k.bin".to_string(),
-                url: Some("https://blob.handy.computer/breeze-asr-q5_k.bin".to_string()),
-                size_mb: 1080,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.35,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: whisper_languages,
-            },
-        );
-
-        // Add NVIDIA Parakeet models (directory-based)
-        available_models.insert(
-            "parakeet-tdt-0.6b-v2".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v2".to_string(),
-                name: "Parakeet V2".to_string(),
-                description: "English only. The best model for English speakers.".to_string(),
-                filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
-                size_mb: 473, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.85,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-            },
-        );
-
-        // Parakeet V3 supported languages (25 EU languages + Russian/Ukrainian):
-        // bg, hr, cs, da, nl, en, et, fi, fr, de, el, hu, it, lv, lt, mt, pl, pt, ro, sk, sl, es, sv, ru, uk
-        let parakeet_v3_languages: Vec<String> = vec![
-            "bg", "hr", "cs", "da", "nl", "en", "et", "fi", "fr", "de", "el", "hu", "it", "lv",
-            "lt", "mt", "pl", "pt", "ro", "sk", "sl", "es", "sv", "ru", "uk",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        available_models.insert(
-            "parakeet-tdt-0.6b-v3".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v3".to_string(),
-                name: "Parakeet V3".to_string(),
-                description: "Fast and accurate. Supports 25 European languages.".to_string(),
-                filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
-                size_mb: 478, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.80,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: true,
-                supported_languages: parakeet_v3_languages,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-base".to_string(),
-            ModelInfo {
-                id: "moonshine-base".to_string(),
-                name: "Moonshine Base".to_string(),
-                description: "Very fast, English only. Handles accents well.".to_string(),
-                filename: "moonshine-base".to_string(),
-                url: Some("https://blob.handy.computer/moonshine-base.tar.gz".to_string()),
-                size_mb: 58,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Moonshine,
-                accuracy_score: 0.70,
-                speed_score: 0.90,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-            },
-        );
+        let available_models = merge_manifests(Self::load_manifests(app_handle, &app_data_dir));
 
         let manager = Self {
             app_handle: app_handle.clone(),
+            app_data_dir,
             models_dir,
             available_models: Mutex::new(available_models),
             cancel_flags: Arc::new(Mutex::new(HashMap::new())),
             extracting_models: Arc::new(Mutex::new(HashSet::new())),
+            download_queue: Mutex::new(DownloadQueue::default()),
+            download_waiters: Mutex::new(HashMap::new()),
+            rate_limiter: Mutex::new(RateLimiter::new(
+                get_settings(app_handle).download_speed_limit,
+            )),
         };
 
         // Migrate any bundled models to user directory
@@ -300,6 +143,254 @@ impl ModelManager {
         Ok(manager)
     }
 
+    /// The bundled manifest, then the cached remote catalog, then the user's manifest.
+    fn load_manifests(app_handle: &AppHandle, app_data_dir: &Path) -> Vec<ModelManifest> {
+        let bundled = bundled_manifest();
+        let mut manifests = vec![bundled.clone()];
+        let remote_enabled = get_settings(app_handle).model_catalog_url.is_some();
+        // (enabled, remote, path)
+        let files = [
+            (
+                remote_enabled,
+                true,
+                app_data_dir.join(REMOTE_CATALOG_CACHE_FILE),
+            ),
+            (true, false, app_data_dir.join(USER_MANIFEST_FILE)),
+        ];
+
+        for (enabled, remote, path) in files {
+            if !enabled || !path.exists() {
+                continue;
+            }
+            let manifest = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| parse_manifest(&json));
+            match manifest {
+                Ok(mut manifest) => {
+                    debug!("Loaded model manifest {:?}", path);
+                    if remote {
+                        pin_bundled_sources(&mut manifest, &bundled);
+                    }
+                    manifests.push(manifest);
+                }
+                Err(e) => warn!("Ignoring model manifest {:?}: {}", path, e),
+            }
+        }
+        manifests
+    }
+
+    /// Re-reads the manifests, keeping the download state of known models. Models
+    /// dropped from the catalog stay listed while they are downloaded.
+    pub fn reload_catalog(&self) -> Result<()> {
+        let mut catalog =
+            merge_manifests(Self::load_manifests(&self.app_handle, &self.app_data_dir));
+        {
+            let mut models = self.available_models.lock().unwrap();
+            for (id, model) in models.iter() {
+                if model.is_downloaded && !catalog.contains_key(id) {
+                    catalog.insert(id.clone(), model.clone());
+                }
+            }
+            *models = catalog;
+        }
+
+        self.update_download_status()?;
+        // Status updates assume nothing is downloading, restore the active downloads
+        {
+            let flags = self.cancel_flags.lock().unwrap();
+            let mut models = self.available_models.lock().unwrap();
+            for id in flags.keys() {
+                if let Some(model) = models.get_mut(id) {
+                    model.is_downloading = true;
+                }
+            }
+        }
+
+        let _ = self.app_handle.emit("model-catalog-updated", ());
+        Ok(())
+    }
+
+    /// Fetches the catalog from `model_catalog_url`, caches it and reloads the catalog.
+    /// Without a URL only the local manifests are re-read.
+    pub async fn refresh_catalog(&self) -> Result<()> {
+        if let Some(url) = get_settings(&self.app_handle).model_catalog_url {
+            if !url.starts_with("https://") {
+                return Err(anyhow::anyhow!("The catalog URL must start with https://"));
+            }
+            let client = self
+                .http_client_builder()?
+                .timeout(Duration::from_secs(30))
+                .build()?;
+            let json = client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            // Validate before caching so a bad response doesn't replace a good catalog
+            let manifest = parse_manifest(&json)?;
+            fs::write(self.app_data_dir.join(REMOTE_CATALOG_CACHE_FILE), json)?;
+            info!(
+                "Fetched model catalog with {} models from {}",
+                manifest.models.len(),
+                url
+            );
+        }
+
+        self.reload_catalog()
+    }
+
+    /// A client builder that goes through `model_download_proxy` when one is set.
+    /// Otherwise reqwest picks up the system proxy from the environment.
+    fn http_client_builder(&self) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = get_settings(&self.app_handle).model_download_proxy {
+            let proxy = reqwest::Proxy::all(&proxy)
+                .map_err(|e| anyhow::anyhow!("Invalid proxy '{}': {}", proxy, e))?;
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+
+    /// Forgets the catalog fetched from a previous `model_catalog_url`.
+    pub fn clear_remote_catalog(&self) -> Result<()> {
+        let path = self.app_data_dir.join(REMOTE_CATALOG_CACHE_FILE);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Copies or links a local model into the models directory and registers it in the
+    /// user manifest, so it stays listed across restarts. The caller is expected to have
+    /// checked that `engine_type` can load it.
+    pub fn import_model(
+        &self,
+        request: &ModelImportRequest,
+        engine_type: EngineType,
+    ) -> Result<ModelInfo> {
+        // Absolute, so symlinks keep working
+        let source = fs::canonicalize(&request.path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", request.path, e))?;
+        let is_directory = source.is_dir();
+        match (&engine_type, is_directory) {
+            (EngineType::Whisper, true) => {
+                return Err(anyhow::anyhow!("Whisper models must be a single GGML file"))
+            }
+            (EngineType::Parakeet | EngineType::Moonshine, false) => {
+                return Err(anyhow::anyhow!(
+                    "{:?} models must be a directory of ONNX files",
+                    engine_type
+                ))
+            }
+            _ => {}
+        }
+
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "model".to_string());
+        let name = request
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .unwrap_or(stem);
+        let id = self.unique_model_id(&name);
+        let filename = match source.extension().filter(|_| !is_directory) {
+            Some(extension) => format!("{}.{}", id, extension.to_string_lossy()),
+            None => id.clone(),
+        };
+
+        let target = self.models_dir.join(&filename);
+        if target.exists() {
+            return Err(anyhow::anyhow!("{:?} already exists", target));
+        }
+        let copied = match request.mode {
+            ImportMode::Copy if is_directory => copy_dir_all(&source, &target),
+            ImportMode::Copy => fs::copy(&source, &target).map(|_| ()),
+            ImportMode::Symlink => symlink(&source, &target, is_directory),
+        };
+        if let Err(e) = copied {
+            // Don't leave a partial copy behind to block the next import
+            if request.mode == ImportMode::Copy {
+                let _ = if is_directory {
+                    fs::remove_dir_all(&target)
+                } else {
+                    fs::remove_file(&target)
+                };
+            }
+            return Err(e.into());
+        }
+
+        let (supported_languages, language_set) = if !request.supported_languages.is_empty() {
+            (request.supported_languages.clone(), None)
+        } else if matches!(engine_type, EngineType::Whisper) {
+            (Vec::new(), Some("whisper".to_string()))
+        } else {
+            (vec!["en".to_string()], None)
+        };
+        let entry = ManifestModel {
+            id: id.clone(),
+            name,
+            description: format!("Imported from {}", request.path),
+            filename,
+            url: None,
+            sha256: None,
+            size_mb: path_size(&source) / (1024 * 1024),
+            is_directory,
+            engine_type,
+            accuracy_score: 0.0,
+            speed_score: 0.0,
+            supports_translation: false,
+            is_recommended: false,
+            supported_languages,
+            language_set,
+        };
+
+        let manifest_path = self.app_data_dir.join(USER_MANIFEST_FILE);
+        if let Err(e) = add_user_model(&manifest_path, entry) {
+            // Don't leave an unlisted copy behind
+            let _ = if is_directory && request.mode == ImportMode::Copy {
+                fs::remove_dir_all(&target)
+            } else {
+                fs::remove_file(&target)
+            };
+            return Err(e);
+        }
+
+        info!("Imported model {} from {}", id, request.path);
+        self.reload_catalog()?;
+        self.get_model_info(&id)
+            .ok_or_else(|| anyhow::anyhow!("Imported model {} is missing from the catalog", id))
+    }
+
+    /// `custom-<name>`, numbered if a model with that id already exists
+    fn unique_model_id(&self, name: &str) -> String {
+        let slug: String = name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let slug = slug
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let base = format!("custom-{}", if slug.is_empty() { "model" } else { &slug });
+
+        let models = self.available_models.lock().unwrap();
+        let mut id = base.clone();
+        let mut n = 2;
+        while models.contains_key(&id) {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        id
+    }
+
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
         models.values().cloned().collect()
@@ -415,7 +506,50 @@ impl ModelManager {
         Ok(())
     }
 
-    pub async fn download_model(&self, model_id: &str) -> Result<()> {
+    /// Queues a model download and waits until it has completed, failed, or was
+    /// paused or cancelled.
+    pub async fn download_model(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.download_waiters
+            .lock()
+            .unwrap()
+            .entry(model_id.to_string())
+            .or_default()
+            .push(sender);
+        if let Err(e) = self.queue_download(model_id) {
+            self.resolve_waiters(model_id, Err(e.to_string()));
+        }
+
+        receiver
+            .await
+            .map_err(|_| anyhow::anyhow!("Download of model {} was dropped", model_id))?
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// Ends the wait of every `download_model` call for the model.
+    fn resolve_waiters(&self, model_id: &str, result: Result<(), String>) {
+        let waiters = self.download_waiters.lock().unwrap().remove(model_id);
+        for waiter in waiters.into_iter().flatten() {
+            let _ = waiter.send(result.clone());
+        }
+    }
+
+    /// Downloads a model and checks it against its catalog checksum. A corrupted
+    /// download is discarded and fetched once more before giving up.
+    async fn download_with_retry(&self, model_id: &str, cancel_flag: &AtomicBool) -> Result<()> {
+        match self.download_model_once(model_id, cancel_flag).await {
+            Err(e) if e.is::<ChecksumMismatch>() => {
+                warn!(
+                    "Download of model {} is corrupted ({}), retrying",
+                    model_id, e
+                );
+                self.download_model_once(model_id, cancel_flag).await
+            }
+            result => result,
+        }
+    }
+
+    async fn download_model_once(&self, model_id: &str, cancel_flag: &AtomicBool) -> Result<()> {
         let model_info = {
             let models = self.available_models.lock().unwrap();
             models.get(model_id).cloned()
@@ -426,7 +560,12 @@ impl ModelManager {
 
         let url = model_info
             .url
+            .clone()
             .ok_or_else(|| anyhow::anyhow!("No download URL for model"))?;
+        let url = match get_settings(&self.app_handle).model_mirror_url {
+            Some(mirror) => mirrored_url(&url, &mirror),
+            None => url,
+        };
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -443,7 +582,7 @@ impl ModelManager {
         }
 
         // Check if we have a partial download to resume
-        let mut resume_from = if partial_path.exists() {
+        let resume_from = if partial_path.exists() {
             let size = partial_path.metadata()?.len();
             info!("Resuming download of model {} from byte {}", model_id, size);
             size
@@ -452,6 +591,12 @@ impl ModelManager {
             0
         };
 
+        // Paused or cancelled while waiting to start
+        if cancel_flag.load(Ordering::Relaxed) {
+            info!("Download of model {} stopped before it started", model_id);
+            return Ok(());
+        }
+
         // Mark as downloading
         {
             let mut models = self.available_models.lock().unwrap();
@@ -460,16 +605,26 @@ impl ModelManager {
             }
         }
 
-        // Create cancellation flag for this download
-        let cancel_flag = Arc::new(AtomicBool::new(false));
-        {
-            let mut flags = self.cancel_flags.lock().unwrap();
-            flags.insert(model_id.to_string(), cancel_flag.clone());
-        }
+        self.transfer_model(model_id, &model_info, &url, resume_from, cancel_flag)
+            .await
+    }
+
+    /// Fetches a model into its `.partial` file, from `resume_from` on, and installs it.
+    async fn transfer_model(
+        &self,
+        model_id: &str,
+        model_info: &ModelInfo,
+        url: &str,
+        mut resume_from: u64,
+        cancel_flag: &AtomicBool,
+    ) -> Result<()> {
+        let partial_path = self
+            .models_dir
+            .join(format!("{}.partial", &model_info.filename));
 
         // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
-        let mut request = client.get(&url);
+        let client = self.http_client_builder()?.build()?;
+        let mut request = client.get(url);
 
         if resume_from > 0 {
             request = request.header("Range", format!("bytes={}-", resume_from));
@@ -492,7 +647,7 @@ impl ModelManager {
             resume_from = 0;
 
             // Restart download without range header
-            response = client.get(&url).send().await?;
+            response = client.get(url).send().await?;
         }
 
         // Check for success or partial content status
@@ -567,12 +722,6 @@ impl ModelManager {
                     }
                 }
 
-                // Remove cancel flag
-                {
-                    let mut flags = self.cancel_flags.lock().unwrap();
-                    flags.remove(model_id);
-                }
-
                 // Keep partial file for resume functionality
                 return Ok(());
             }
@@ -591,6 +740,16 @@ impl ModelManager {
             file.write_all(&chunk)?;
             downloaded += chunk.len() as u64;
 
+            // The limiter is shared, so the limit covers all running downloads together
+            let delay = self
+                .rate_limiter
+                .lock()
+                .unwrap()
+                .consume(chunk.len() as u64, Instant::now());
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
             let percentage = if total_size > 0 {
                 (downloaded as f64 / total_size as f64) * 100.0
             } else {
@@ -648,6 +807,44 @@ impl ModelManager {
             }
         }
 
+        self.install_download(model_id, model_info, &partial_path)
+    }
+
+    /// Checks a completed `.partial` download against the catalog checksum and moves it
+    /// into place, extracting it first for directory models.
+    fn install_download(
+        &self,
+        model_id: &str,
+        model_info: &ModelInfo,
+        partial_path: &Path,
+    ) -> Result<()> {
+        let model_path = self.models_dir.join(&model_info.filename);
+
+        // Catch corrupted downloads here rather than as a failure to load the model
+        let verified_digest = match &model_info.sha256 {
+            Some(expected) => {
+                let _ = self.app_handle.emit("model-verification-started", model_id);
+                let actual = sha256_file(partial_path)?;
+                if !digests_match(expected, &actual) {
+                    let _ = fs::remove_file(partial_path);
+                    {
+                        let mut models = self.available_models.lock().unwrap();
+                        if let Some(model) = models.get_mut(model_id) {
+                            model.is_downloading = false;
+                        }
+                    }
+                    let _ = self.app_handle.emit("model-verification-failed", model_id);
+                    return Err(ChecksumMismatch {
+                        expected: expected.clone(),
+                        actual,
+                    }
+                    .into());
+                }
+                Some(actual)
+            }
+            None => None,
+        };
+
         // Handle directory-based models (extract tar.gz) vs file-based models
         if model_info.is_directory {
             // Track that this model is being extracted
@@ -675,7 +872,7 @@ impl ModelManager {
             fs::create_dir_all(&temp_extract_dir)?;
 
             // Open the downloaded tar.gz file
-            let tar_gz = File::open(&partial_path)?;
+            let tar_gz = File::open(partial_path)?;
             let tar = GzDecoder::new(tar_gz);
             let mut archive = Archive::new(tar);
 
@@ -732,10 +929,24 @@ impl ModelManager {
             let _ = self.app_handle.emit("model-extraction-completed", model_id);
 
             // Remove the downloaded tar.gz file
-            let _ = fs::remove_file(&partial_path);
+            let _ = fs::remove_file(partial_path);
         } else {
             // Move partial file to final location for file-based models
-            fs::rename(&partial_path, &model_path)?;
+            fs::rename(partial_path, &model_path)?;
+        }
+
+        // Record what was installed so `check_model` can detect later corruption
+        let digest = match verified_digest {
+            Some(digest) if !model_info.is_directory => Ok(digest),
+            _ => model_digest(&model_path),
+        };
+        if let Err(e) = digest.and_then(|digest| {
+            fs::write(
+                checksum_path(&self.models_dir, &model_info.filename),
+                digest,
+            )
+        }) {
+            warn!("Failed to record checksum of model {}: {}", model_id, e);
         }
 
         // Update download status
@@ -765,6 +976,95 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Installs a catalog model from a `.bin` or `.tar.gz` downloaded on another
+    /// machine. The file is checked and extracted exactly like a download. Without
+    /// `model_id` the model is found by the file name.
+    pub fn install_model_bundle(&self, model_id: Option<&str>, path: &Path) -> Result<ModelInfo> {
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("{:?} is not a file", path));
+        }
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let model_info = match model_id {
+            Some(model_id) => self
+                .get_model_info(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?,
+            None => self
+                .get_available_models()
+                .into_iter()
+                .find(|model| bundle_matches(model, &file_name))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No model in the catalog matches {}, please choose one",
+                        file_name
+                    )
+                })?,
+        };
+
+        // Claim the model like a download, so neither a download nor a second install
+        // writes the same files meanwhile
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        {
+            let mut flags = self.cancel_flags.lock().unwrap();
+            let mut models = self.available_models.lock().unwrap();
+            let model = models
+                .get_mut(&model_info.id)
+                .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_info.id))?;
+            if model.is_downloaded {
+                return Err(anyhow::anyhow!(
+                    "Model {} is already installed",
+                    model_info.id
+                ));
+            }
+            if model.is_downloading || flags.contains_key(&model_info.id) {
+                return Err(anyhow::anyhow!(
+                    "Model {} is currently downloading",
+                    model_info.id
+                ));
+            }
+            model.is_downloading = true;
+            flags.insert(model_info.id.clone(), cancel_flag.clone());
+        }
+
+        // A bundle replaces any partial download of the same model
+        let partial_path = self
+            .models_dir
+            .join(format!("{}.partial", &model_info.filename));
+        let installed = fs::copy(path, &partial_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| self.install_download(&model_info.id, &model_info, &partial_path));
+        if let Err(e) = installed {
+            let _ = fs::remove_file(&partial_path);
+            self.release_download(&model_info.id, &cancel_flag);
+            return Err(e);
+        }
+
+        info!("Installed model {} from {:?}", model_info.id, path);
+        self.get_model_info(&model_info.id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_info.id))
+    }
+
+    /// Clears the downloading state of a model whose transfer or install stopped.
+    /// The flag is only dropped if it is still `cancel_flag`, not one registered
+    /// since by resuming the download.
+    fn release_download(&self, model_id: &str, cancel_flag: &Arc<AtomicBool>) {
+        {
+            let mut flags = self.cancel_flags.lock().unwrap();
+            if flags
+                .get(model_id)
+                .is_some_and(|flag| Arc::ptr_eq(flag, cancel_flag))
+            {
+                flags.remove(model_id);
+            }
+        }
+        if let Some(model) = self.available_models.lock().unwrap().get_mut(model_id) {
+            model.is_downloading = false;
+        }
+    }
+
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: delete_model called for: {}", model_id);
 
@@ -817,6 +1117,16 @@ impl ModelManager {
             return Err(anyhow::anyhow!("No model files found to delete"));
         }
 
+        let _ = fs::remove_file(checksum_path(&self.models_dir, &model_info.filename));
+
+        // Imported models can't be downloaded again, so they leave the catalog
+        if model_info.url.is_none()
+            && remove_user_model(&self.app_data_dir.join(USER_MANIFEST_FILE), model_id)?
+        {
+            info!("Removed imported model {} from the user manifest", model_id);
+            self.reload_catalog()?;
+        }
+
         // Update download status
         self.update_download_status()?;
         debug!("ModelManager: download status updated");
@@ -872,9 +1182,238 @@ impl ModelManager {
         }
     }
 
+    /// Compares an installed model with its checksum: the catalog's for model files,
+    /// otherwise the digest recorded when it was installed. Returns `None` when
+    /// neither is known.
+    pub fn check_model(&self, model_id: &str) -> Result<Option<bool>> {
+        let model_path = self.get_model_path(model_id)?;
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        let expected = model_info
+            .sha256
+            .filter(|_| !model_info.is_directory)
+            .or_else(|| {
+                read_recorded_digest(&checksum_path(&self.models_dir, &model_info.filename))
+            });
+        let Some(expected) = expected else {
+            return Ok(None);
+        };
+
+        let actual = model_digest(&model_path)?;
+        let matches = digests_match(&expected, &actual);
+        if !matches {
+            warn!(
+                "Model {} is corrupted: expected SHA-256 {}, got {}",
+                model_id, expected, actual
+            );
+        }
+        Ok(Some(matches))
+    }
+
+    /// Replaces the installed files of a model with a fresh download, through the
+    /// download queue.
+    pub async fn redownload_model(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if model_info.url.is_none() {
+            return Err(anyhow::anyhow!(
+                "Model {} was imported from a local file and can't be downloaded again",
+                model_id
+            ));
+        }
+
+        let model_path = self.models_dir.join(&model_info.filename);
+        if model_info.is_directory && model_path.is_dir() {
+            fs::remove_dir_all(&model_path)?;
+        } else if model_path.exists() {
+            fs::remove_file(&model_path)?;
+        }
+        let _ = fs::remove_file(checksum_path(&self.models_dir, &model_info.filename));
+        self.update_download_status()?;
+
+        info!("Downloading model {} again", model_id);
+        self.download_model(model_id).await
+    }
+
+    /// Adds a model to the download queue. It starts once fewer than
+    /// `max_concurrent_downloads` are running; failures are reported with the
+    /// `model-download-failed` event.
+    fn queue_download(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if model_info.url.is_none() {
+            return Err(anyhow::anyhow!("No download URL for model"));
+        }
+        if model_info.is_downloaded {
+            self.resolve_waiters(model_id, Ok(()));
+            return Ok(());
+        }
+
+        {
+            let mut flags = self.cancel_flags.lock().unwrap();
+            let mut queue = self.download_queue.lock().unwrap();
+            if flags.contains_key(model_id) && !queue.contains(model_id) {
+                return Err(anyhow::anyhow!(
+                    "Model {} is currently being installed",
+                    model_id
+                ));
+            }
+            // The flag exists from here on, so a pause before the transfer starts stops it
+            if queue.enqueue(model_id) {
+                info!("Queued download of model {}", model_id);
+                flags.insert(model_id.to_string(), Arc::new(AtomicBool::new(false)));
+            }
+        }
+        self.start_queued_downloads();
+        Ok(())
+    }
+
+    /// Starts queued downloads while there are free slots.
+    pub fn start_queued_downloads(self: &Arc<Self>) {
+        let max_concurrent = get_settings(&self.app_handle)
+            .max_concurrent_downloads
+            .max(1) as usize;
+        loop {
+            let next = self
+                .download_queue
+                .lock()
+                .unwrap()
+                .next_to_start(max_concurrent);
+            let Some(model_id) = next else {
+                break;
+            };
+
+            let cancel_flag = self
+                .cancel_flags
+                .lock()
+                .unwrap()
+                .entry(model_id.clone())
+                .or_insert_with(|| Arc::new(AtomicBool::new(false)))
+                .clone();
+            let manager = self.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = manager
+                    .download_with_retry(&model_id, &cancel_flag)
+                    .await
+                    .map_err(|e| e.to_string());
+                if let Err(e) = &result {
+                    error!("Download of model {} failed: {}", model_id, e);
+                    let _ = manager.app_handle.emit(
+                        "model-download-failed",
+                        &serde_json::json!({
+                            "model_id": model_id,
+                            "error": e
+                        }),
+                    );
+                }
+                manager.release_download(&model_id, &cancel_flag);
+                let requeued = {
+                    let mut queue = manager.download_queue.lock().unwrap();
+                    queue.finish(&model_id);
+                    queue.is_queued(&model_id)
+                };
+                // A download resumed before this transfer stopped reports when it ends
+                if !requeued {
+                    manager.resolve_waiters(&model_id, result);
+                }
+                manager.start_queued_downloads();
+            });
+        }
+        self.emit_download_queue();
+    }
+
+    /// Moves a queued download ahead of the others.
+    pub fn prioritize_download(&self, model_id: &str) -> Result<()> {
+        if !self.download_queue.lock().unwrap().prioritize(model_id) {
+            return Err(anyhow::anyhow!(
+                "Model {} is not waiting in the download queue",
+                model_id
+            ));
+        }
+        self.emit_download_queue();
+        Ok(())
+    }
+
+    /// Pauses a queued or running download. A running one keeps its `.partial` file,
+    /// so resuming continues from there with a Range request.
+    pub fn pause_download(&self, model_id: &str) -> Result<()> {
+        let paused_from = self.download_queue.lock().unwrap().pause(model_id);
+        match paused_from {
+            PausedFrom::NotQueued => {
+                return Err(anyhow::anyhow!(
+                    "Model {} is not in the download queue",
+                    model_id
+                ))
+            }
+            PausedFrom::Active => self.stop_transfer(model_id)?,
+            PausedFrom::Queued => {
+                // Resuming registers a new flag
+                self.cancel_flags.lock().unwrap().remove(model_id);
+                self.resolve_waiters(model_id, Ok(()));
+            }
+        }
+        info!("Paused download of model {}", model_id);
+        self.emit_download_queue();
+        Ok(())
+    }
+
+    pub fn resume_download(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        if !self.download_queue.lock().unwrap().resume(model_id) {
+            return Err(anyhow::anyhow!(
+                "Download of model {} is not paused",
+                model_id
+            ));
+        }
+        info!("Resumed download of model {}", model_id);
+        self.start_queued_downloads();
+        Ok(())
+    }
+
+    pub fn get_download_queue(&self) -> DownloadQueueState {
+        self.download_queue.lock().unwrap().state()
+    }
+
+    pub fn set_download_speed_limit(&self, bytes_per_second: Option<u64>) {
+        self.rate_limiter
+            .lock()
+            .unwrap()
+            .set_limit(bytes_per_second);
+    }
+
+    fn emit_download_queue(&self) {
+        let state = self.get_download_queue();
+        let _ = self.app_handle.emit("model-download-queue", &state);
+    }
+
     pub fn cancel_download(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: cancel_download called for: {}", model_id);
 
+        let running = {
+            let mut queue = self.download_queue.lock().unwrap();
+            queue.remove(model_id);
+            queue.is_running(model_id)
+        };
+        self.stop_transfer(model_id)?;
+        // A running transfer clears its own state once it has stopped
+        if !running {
+            self.cancel_flags.lock().unwrap().remove(model_id);
+            self.resolve_waiters(model_id, Ok(()));
+        }
+
+        // Emit cancellation event so all UI components can clear their state
+        let _ = self.app_handle.emit("model-download-cancelled", model_id);
+        self.emit_download_queue();
+
+        info!("Download cancellation initiated for: {}", model_id);
+        Ok(())
+    }
+
+    /// Stops a running download after its current chunk, keeping the `.partial` file.
+    fn stop_transfer(&self, model_id: &str) -> Result<()> {
         // Set the cancellation flag to stop the download loop
         {
             let flags = self.cancel_flags.lock().unwrap();
@@ -895,12 +1434,62 @@ impl ModelManager {
         }
 
         // Update download status to reflect current state
-        self.update_download_status()?;
+        self.update_download_status()
+    }
+}
 
-        // Emit cancellation event so all UI components can clear their state
-        let _ = self.app_handle.emit("model-download-cancelled", model_id);
+/// Whether `file_name` is what downloading `model` would fetch
+fn bundle_matches(model: &ModelInfo, file_name: &str) -> bool {
+    let download_name = model
+        .url
+        .as_deref()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| !name.is_empty());
+    download_name == Some(file_name)
+        || (!model.is_directory && model.filename == file_name)
+        || (model.is_directory && file_name == format!("{}.tar.gz", model.filename))
+}
 
-        info!("Download cancellation initiated for: {}", model_id);
-        Ok(())
+fn copy_dir_all(source: &Path, target: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            copy_dir_all(&path, &target.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, target.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, target: &Path, _is_directory: bool) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, target: &Path, is_directory: bool) -> std::io::Result<()> {
+    if is_directory {
+        std::os::windows::fs::symlink_dir(source, target)
+    } else {
+        std::os::windows::fs::symlink_file(source, target)
+    }
+}
+
+/// Size of a file, or of all files below a directory, in bytes
+fn path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| path_size(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or(0)
+    } else {
+        path.metadata().map(|m| m.len()).unwrap_or(0)
     }
 }
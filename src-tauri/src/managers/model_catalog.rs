@@ -0,0 +1,386 @@
+//! The model catalog: which models exist and where to download them.
+//!
+//! The catalog is a versioned JSON manifest. The one bundled with the app is merged
+//! with the last catalog fetched from `model_catalog_url` and with a user-editable
+//! manifest in the app data dir, in that order. Later manifests replace models with
+//! the same id, so new builds can be listed without releasing the app. A fetched
+//! catalog can't change where bundled models are downloaded from or their checksums.
+
+use crate::managers::model::{EngineType, ModelInfo};
+use crate::managers::model_checksum::is_valid_digest;
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The newest manifest format this build understands
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// User-editable manifest in the app data dir
+pub const USER_MANIFEST_FILE: &str = "model_manifest.json";
+/// Last catalog fetched from `model_catalog_url`, in the app data dir
+pub const REMOTE_CATALOG_CACHE_FILE: &str = "model_catalog_cache.json";
+
+const BUNDLED_MANIFEST: &str = include_str!("../../resources/models.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelManifest {
+    pub version: u32,
+    /// Named language lists that models can refer to with `language_set`
+    #[serde(default)]
+    pub language_sets: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub models: Vec<ManifestModel>,
+}
+
+/// A catalog entry. Download state is not part of the manifest, it is filled in
+/// by `ModelManager` from the models directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestModel {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// File name, or directory name for models shipped as a `.tar.gz` archive
+    pub filename: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// SHA-256 of the download: the model file, or the archive for directory models
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size_mb: u64,
+    #[serde(default)]
+    pub is_directory: bool,
+    pub engine_type: EngineType,
+    #[serde(default)]
+    pub accuracy_score: f32,
+    #[serde(default)]
+    pub speed_score: f32,
+    #[serde(default)]
+    pub supports_translation: bool,
+    #[serde(default)]
+    pub is_recommended: bool,
+    #[serde(default)]
+    pub supported_languages: Vec<String>,
+    /// Name of a manifest language set, added to `supported_languages`
+    #[serde(default)]
+    pub language_set: Option<String>,
+}
+
+impl ManifestModel {
+    fn into_model_info(self, language_sets: &HashMap<String, Vec<String>>) -> ModelInfo {
+        let mut supported_languages = self.supported_languages;
+        if let Some(set) = &self.language_set {
+            match language_sets.get(set) {
+                Some(languages) => supported_languages.extend(languages.iter().cloned()),
+                None => warn!("Model '{}' uses unknown language set '{}'", self.id, set),
+            }
+        }
+
+        ModelInfo {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            filename: self.filename,
+            url: self.url,
+            sha256: self.sha256.map(|digest| digest.to_lowercase()),
+            size_mb: self.size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: self.is_directory,
+            engine_type: self.engine_type,
+            accuracy_score: self.accuracy_score,
+            speed_score: self.speed_score,
+            supports_translation: self.supports_translation,
+            is_recommended: self.is_recommended,
+            supported_languages,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            return Err(anyhow!("Model entry has an empty id"));
+        }
+        // The filename is joined to the models directory, so it must stay inside it
+        let filename = self.filename.trim();
+        if filename.is_empty()
+            || filename == "."
+            || filename == ".."
+            || filename.contains(['/', '\\'])
+        {
+            return Err(anyhow!(
+                "Model '{}' has an invalid filename '{}'",
+                self.id,
+                self.filename
+            ));
+        }
+        if let Some(digest) = &self.sha256 {
+            if !is_valid_digest(digest) {
+                return Err(anyhow!(
+                    "Model '{}' has an invalid SHA-256 checksum '{}'",
+                    self.id,
+                    digest
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a manifest, rejecting formats newer than this build understands.
+pub fn parse_manifest(json: &str) -> Result<ModelManifest> {
+    let manifest: ModelManifest =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid model manifest: {}", e))?;
+    if manifest.version == 0 || manifest.version > MANIFEST_VERSION {
+        return Err(anyhow!(
+            "Unsupported model manifest version {} (expected at most {})",
+            manifest.version,
+            MANIFEST_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+/// The catalog shipped with the app.
+pub fn bundled_manifest() -> ModelManifest {
+    parse_manifest(BUNDLED_MANIFEST).expect("bundled model manifest is valid")
+}
+
+/// Merges manifests into the catalog. Later manifests win, both for language sets
+/// and for models with the same id. Invalid entries are skipped with a warning.
+pub fn merge_manifests(manifests: Vec<ModelManifest>) -> HashMap<String, ModelInfo> {
+    let mut language_sets: HashMap<String, Vec<String>> = HashMap::new();
+    for manifest in &manifests {
+        language_sets.extend(manifest.language_sets.clone());
+    }
+
+    let mut models = HashMap::new();
+    for model in manifests.into_iter().flat_map(|manifest| manifest.models) {
+        if let Err(e) = model.validate() {
+            warn!("Skipping model catalog entry: {}", e);
+            continue;
+        }
+        models.insert(model.id.clone(), model.into_model_info(&language_sets));
+    }
+    models
+}
+
+/// Keeps the download source of bundled models in a fetched catalog: its entries
+/// may describe a bundled model differently, but not change what gets downloaded.
+pub fn pin_bundled_sources(remote: &mut ModelManifest, bundled: &ModelManifest) {
+    for model in &mut remote.models {
+        if let Some(original) = bundled.models.iter().find(|m| m.id == model.id) {
+            model.url = original.url.clone();
+            model.sha256 = original.sha256.clone();
+            model.filename = original.filename.clone();
+            model.is_directory = original.is_directory;
+        }
+    }
+}
+
+/// Points a download URL at `mirror`, keeping only the file name, so
+/// `https://blob.handy.computer/ggml-small.bin` becomes `<mirror>/ggml-small.bin`.
+pub fn mirrored_url(url: &str, mirror: &str) -> String {
+    let file_name = url.rsplit('/').next().unwrap_or(url);
+    format!("{}/{}", mirror.trim_end_matches('/'), file_name)
+}
+
+fn read_user_manifest(path: &Path) -> Result<ModelManifest> {
+    if !path.exists() {
+        return Ok(ModelManifest {
+            version: MANIFEST_VERSION,
+            language_sets: HashMap::new(),
+            models: Vec::new(),
+        });
+    }
+    parse_manifest(&fs::read_to_string(path)?)
+}
+
+/// Adds `model` to the user manifest at `path`, replacing an entry with the same id.
+/// An unreadable manifest is left alone rather than overwritten.
+pub fn add_user_model(path: &Path, model: ManifestModel) -> Result<()> {
+    let mut manifest = read_user_manifest(path)?;
+    manifest.models.retain(|m| m.id != model.id);
+    manifest.models.push(model);
+    fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
+/// Removes the model `id` from the user manifest. Returns whether it was listed.
+pub fn remove_user_model(path: &Path, id: &str) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let mut manifest = read_user_manifest(path)?;
+    let len = manifest.models.len();
+    manifest.models.retain(|m| m.id != id);
+    if manifest.models.len() == len {
+        return Ok(false);
+    }
+    fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(true)
+}
+
+/// Guesses the engine of a model on disk: Whisper models are single GGML `.bin`
+/// files, Parakeet and Moonshine models are directories of ONNX files.
+pub fn infer_engine_type(path: &Path) -> Option<EngineType> {
+    if path.is_file() {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        return (extension == "bin").then_some(EngineType::Whisper);
+    }
+
+    let names: Vec<String> = fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_lowercase())
+        .collect();
+    engine_for_directory(&names)
+}
+
+fn engine_for_directory(names: &[String]) -> Option<EngineType> {
+    let has_onnx = |prefix: &str| {
+        names
+            .iter()
+            .any(|name| name.starts_with(prefix) && name.ends_with(".onnx"))
+    };
+    if has_onnx("encoder-model") && has_onnx("decoder_joint-model") {
+        Some(EngineType::Parakeet)
+    } else if has_onnx("encoder_model") && has_onnx("decoder_model_merged") {
+        Some(EngineType::Moonshine)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_manifest_lists_the_default_models() {
+        let models = merge_manifests(vec![bundled_manifest()]);
+        assert_eq!(models.len(), 8);
+
+        let small = &models["small"];
+        assert_eq!(small.filename, "ggml-small.bin");
+        assert!(small.supported_languages.contains(&"zh-Hant".to_string()));
+
+        let parakeet = &models["parakeet-tdt-0.6b-v3"];
+        assert!(parakeet.is_directory);
+        assert!(parakeet.is_recommended);
+        assert_eq!(parakeet.supported_languages.len(), 25);
+    }
+
+    #[test]
+    #[ignore = "run `bun run update:model-checksums` to fill in the published checksums"]
+    fn bundled_models_have_checksums() {
+        let missing: Vec<String> = bundled_manifest()
+            .models
+            .into_iter()
+            .filter(|model| model.url.is_some() && model.sha256.is_none())
+            .map(|model| model.id)
+            .collect();
+        assert!(missing.is_empty(), "No SHA-256 for {:?}", missing);
+    }
+
+    #[test]
+    fn later_manifests_override_and_extend() {
+        let user = parse_manifest(
+            r#"{
+                "version": 1,
+                "models": [
+                    {"id": "small", "name": "My Small", "filename": "ggml-small.bin",
+                     "engine_type": "Whisper", "language_set": "whisper"},
+                    {"id": "distil", "name": "Distil", "filename": "ggml-distil.bin",
+                     "engine_type": "Whisper", "supported_languages": ["en"]},
+                    {"id": "bad", "name": "Bad", "filename": "../escape.bin",
+                     "engine_type": "Whisper"},
+                    {"id": "bad-hash", "name": "Bad Hash", "filename": "bad.bin",
+                     "engine_type": "Whisper", "sha256": "not-a-digest"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let models = merge_manifests(vec![bundled_manifest(), user]);
+        assert_eq!(models["small"].name, "My Small");
+        // Language sets from earlier manifests stay available
+        assert!(models["small"].supported_languages.len() > 90);
+        assert_eq!(models["distil"].supported_languages, vec!["en"]);
+        assert!(!models.contains_key("bad"));
+        assert!(!models.contains_key("bad-hash"));
+    }
+
+    #[test]
+    fn remote_catalogs_keep_bundled_sources() {
+        let mut remote = parse_manifest(
+            r#"{
+                "version": 1,
+                "models": [
+                    {"id": "small", "name": "Small v2", "filename": "evil.bin",
+                     "url": "https://example.com/evil.bin",
+                     "sha256": "0000000000000000000000000000000000000000000000000000000000000000",
+                     "engine_type": "Whisper"},
+                    {"id": "distil", "name": "Distil", "filename": "ggml-distil.bin",
+                     "url": "https://example.com/ggml-distil.bin", "engine_type": "Whisper"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let bundled = bundled_manifest();
+        pin_bundled_sources(&mut remote, &bundled);
+
+        let models = merge_manifests(vec![bundled.clone(), remote]);
+        let original = bundled.models.iter().find(|m| m.id == "small").unwrap();
+        assert_eq!(models["small"].name, "Small v2");
+        assert_eq!(models["small"].url, original.url);
+        assert_eq!(models["small"].sha256, original.sha256);
+        assert_eq!(models["small"].filename, "ggml-small.bin");
+        assert_eq!(
+            models["distil"].url.as_deref(),
+            Some("https://example.com/ggml-distil.bin")
+        );
+    }
+
+    #[test]
+    fn infers_engine_from_directory_contents() {
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(matches!(
+            engine_for_directory(&names(&[
+                "encoder-model.int8.onnx",
+                "decoder_joint-model.int8.onnx",
+                "vocab.txt"
+            ])),
+            Some(EngineType::Parakeet)
+        ));
+        assert!(matches!(
+            engine_for_directory(&names(&["encoder_model.onnx", "decoder_model_merged.onnx"])),
+            Some(EngineType::Moonshine)
+        ));
+        assert!(engine_for_directory(&names(&["model.safetensors"])).is_none());
+    }
+
+    #[test]
+    fn mirrors_keep_the_file_name() {
+        let url = "https://blob.handy.computer/ggml-small.bin";
+        assert_eq!(
+            mirrored_url(url, "https://models.example.com/handy/"),
+            "https://models.example.com/handy/ggml-small.bin"
+        );
+        assert_eq!(
+            mirrored_url(url, "http://10.0.0.5:8080"),
+            "http://10.0.0.5:8080/ggml-small.bin"
+        );
+    }
+
+    #[test]
+    fn rejects_newer_manifest_versions() {
+        assert!(parse_manifest(r#"{"version": 2, "models": []}"#).is_err());
+        assert!(parse_manifest(r#"{"models": []}"#).is_err());
+        assert!(parse_manifest(r#"{"version": 1}"#).is_ok());
+    }
+}
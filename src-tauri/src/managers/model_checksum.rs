@@ -0,0 +1,162 @@
+//! SHA-256 checksums of model files, used to catch truncated or corrupted downloads.
+//!
+//! The catalog may list the expected digest of a download. Once a model is installed
+//! its digest is also recorded next to it, so extracted directory models and models
+//! without a catalog checksum can be checked later.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Result of checking an installed model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelVerification {
+    /// The files match their checksum
+    Verified,
+    /// No checksum is known for the model, so it could not be checked
+    Unverifiable,
+    /// The files were corrupted and the model was downloaded again
+    Redownloaded,
+}
+
+/// A download or installed model whose digest differs from the expected one
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch: expected SHA-256 {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Whether `digest` is a SHA-256 digest written as 64 hex characters
+pub fn is_valid_digest(digest: &str) -> bool {
+    digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Compares digests, ignoring case
+pub fn digests_match(expected: &str, actual: &str) -> bool {
+    expected.trim().eq_ignore_ascii_case(actual.trim())
+}
+
+/// The lowercase hex SHA-256 digest of a file.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// The digest of an installed model. For directories this hashes every file's path
+/// relative to the directory and its digest, in sorted order, so the result doesn't
+/// depend on the order the file system lists them in.
+pub fn model_digest(path: &Path) -> io::Result<String> {
+    if !path.is_dir() {
+        return sha256_file(path);
+    }
+
+    let mut files = Vec::new();
+    collect_files(path, path, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let digest = sha256_file(&path.join(&relative))?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(digest.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            let relative = path.strip_prefix(root).map_err(io::Error::other)?;
+            let relative: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            files.push(relative.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Where the digest of the installed model `filename` is recorded
+pub fn checksum_path(models_dir: &Path, filename: &str) -> PathBuf {
+    models_dir.join(format!("{}.sha256", filename))
+}
+
+/// The digest recorded for an installed model, if any
+pub fn read_recorded_digest(path: &Path) -> Option<String> {
+    let digest = fs::read_to_string(path).ok()?;
+    let digest = digest.trim();
+    is_valid_digest(digest).then(|| digest.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("handy-checksum-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn hashes_files() {
+        let dir = temp_dir("file");
+        let file = dir.join("model.bin");
+        fs::write(&file, "abc").unwrap();
+        let digest = model_digest(&file).unwrap();
+        assert_eq!(
+            digest,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(is_valid_digest(&digest));
+        assert!(digests_match(&digest.to_uppercase(), &digest));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn directory_digest_covers_names_and_contents() {
+        let dir = temp_dir("dir");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("vocab.txt"), "a b c").unwrap();
+        fs::write(dir.join("nested").join("encoder.onnx"), "weights").unwrap();
+        let original = model_digest(&dir).unwrap();
+
+        fs::write(dir.join("nested").join("encoder.onnx"), "weightz").unwrap();
+        let modified = model_digest(&dir).unwrap();
+        assert_ne!(original, modified);
+
+        fs::write(dir.join("nested").join("encoder.onnx"), "weights").unwrap();
+        assert_eq!(model_digest(&dir).unwrap(), original);
+
+        fs::rename(dir.join("vocab.txt"), dir.join("tokens.txt")).unwrap();
+        assert_ne!(model_digest(&dir).unwrap(), original);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
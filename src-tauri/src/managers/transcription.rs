@@ -1,14 +1,20 @@
-use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
-use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::{
+    apply_custom_words, apply_spoken_punctuation, detect_language, CompiledReplacements,
+    DetectedLanguage, SpokenToken, TranscriptionFilter,
+};
+use crate::managers::model::{EngineType, ModelInfo, ModelManager};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use transcribe_rs::{
     engines::{
         moonshine::{ModelVariant, MoonshineEngine, MoonshineModelParams},
@@ -17,7 +23,7 @@ use transcribe_rs::{
         },
         whisper::{WhisperEngine, WhisperInferenceParams},
     },
-    TranscriptionEngine,
+    TranscriptionEngine, TranscriptionResult,
 };
 
 #[derive(Clone, Debug, Serialize)]
@@ -28,12 +34,313 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct PartialTranscriptionEvent {
+    /// Text of the newly committed chunk, to be appended to the earlier ones
+    pub text: String,
+    /// Position of the chunk in the recording, starting at 0
+    pub chunk_index: usize,
+}
+
+/// A span of transcribed text, timed in seconds from the start of the recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct TimedText {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+/// Final transcription text together with whatever timing information the engine produced.
+/// `words` is only available for engines with word-level timestamps (Parakeet).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct Transcription {
+    pub text: String,
+    pub segments: Vec<TimedText>,
+    pub words: Option<Vec<TimedText>>,
+    /// Language guessed from the text, only set when `selected_language` is "auto"
+    /// and the output is not translated
+    #[serde(default)]
+    pub language: Option<DetectedLanguage>,
+    /// Engine output before corrections, filtering and spoken punctuation, which
+    /// voice commands are matched against
+    #[serde(skip)]
+    pub raw_text: String,
+}
+
+impl Transcription {
+    /// The language the text is in: the detected one in "auto" mode, otherwise `selected`.
+    pub fn language_or<'a>(&'a self, selected: &'a str) -> &'a str {
+        self.language
+            .as_ref()
+            .map_or(selected, |language| language.code.as_str())
+    }
+
+    /// Shifts all timestamps by `offset` seconds, used when stitching streamed chunks.
+    fn offset_by(mut self, offset: f32) -> Self {
+        let shift = |t: &mut TimedText| {
+            t.start += offset;
+            t.end += offset;
+        };
+        self.segments.iter_mut().for_each(shift);
+        if let Some(words) = self.words.as_mut() {
+            words.iter_mut().for_each(shift);
+        }
+        self
+    }
+
+    /// Concatenates consecutive transcriptions into one.
+    fn join(parts: Vec<Transcription>) -> Self {
+        let text = parts
+            .iter()
+            .map(|p| p.text.as_str())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let has_words = !parts.is_empty() && parts.iter().all(|p| p.words.is_some());
+        let words = has_words.then(|| {
+            parts
+                .iter()
+                .flat_map(|p| p.words.clone().unwrap_or_default())
+                .collect()
+        });
+        let segments = parts.into_iter().flat_map(|p| p.segments).collect();
+        Self {
+            text,
+            segments,
+            words,
+            language: None,
+            raw_text: String::new(),
+        }
+    }
+}
+
+/// Groups word timestamps into sentence-level segments.
+fn segments_from_words(words: &[TimedText]) -> Vec<TimedText> {
+    let mut segments = Vec::new();
+    let mut current: Vec<&TimedText> = Vec::new();
+
+    for word in words {
+        current.push(word);
+        if word.text.ends_with(['.', '?', '!', '。', '？', '！']) {
+            segments.push(merge_timed(&current));
+            current.clear();
+        }
+    }
+    if !current.is_empty() {
+        segments.push(merge_timed(&current));
+    }
+    segments
+}
+
+fn merge_timed(parts: &[&TimedText]) -> TimedText {
+    TimedText {
+        start: parts.first().map(|p| p.start).unwrap_or_default(),
+        end: parts.last().map(|p| p.end).unwrap_or_default(),
+        text: parts
+            .iter()
+            .map(|p| p.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// How far ahead [`align_words`] looks for the two word lists to line up again
+const ALIGN_LOOKAHEAD: usize = 8;
+
+/// Word comparison key: lowercase letters and digits only, so added punctuation
+/// and capitalization still match the original word.
+fn word_key(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Maps the words of `finalized` (the joined `words` after post-processing) back
+/// onto the timed words they came from. Unchanged words keep their timing; a run
+/// of changed words takes the timing of the original words it replaced, so
+/// "chat gpt" corrected to "ChatGPT" spans both. Removed words are dropped.
+fn align_words(words: &[TimedText], finalized: &str) -> Vec<TimedText> {
+    let tokens: Vec<&str> = finalized.split_whitespace().collect();
+    let word_keys: Vec<String> = words.iter().map(|w| word_key(&w.text)).collect();
+    let token_keys: Vec<String> = tokens.iter().map(|t| word_key(t)).collect();
+    let same = |i: usize, j: usize| !word_keys[i].is_empty() && word_keys[i] == token_keys[j];
+
+    let mut aligned = Vec::with_capacity(tokens.len());
+    let (mut i, mut j) = (0, 0);
+    while j < tokens.len() {
+        if i < words.len() && same(i, j) {
+            aligned.push(TimedText {
+                text: tokens[j].to_string(),
+                ..words[i].clone()
+            });
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        // Find the closest point where both lists line up again; the words in
+        // between were changed. Near the end the rest is one change, otherwise
+        // fall back to a single changed word.
+        let (words_left, tokens_left) = (words.len() - i, tokens.len() - j);
+        let (skip_words, skip_tokens) = (0..=ALIGN_LOOKAHEAD * 2)
+            .flat_map(|total| (0..=total).map(move |di| (di, total - di)))
+            .filter(|&(di, dj)| di <= ALIGN_LOOKAHEAD && dj <= ALIGN_LOOKAHEAD)
+            .find(|&(di, dj)| di < words_left && dj < tokens_left && same(i + di, j + dj))
+            .unwrap_or(
+                if words_left <= ALIGN_LOOKAHEAD && tokens_left <= ALIGN_LOOKAHEAD {
+                    (words_left, tokens_left)
+                } else {
+                    (1.min(words_left), 1)
+                },
+            );
+
+        let replaced = &words[i..i + skip_words];
+        for (k, token) in tokens[j..j + skip_tokens].iter().enumerate() {
+            let (start, end) = if replaced.is_empty() {
+                // Inserted words sit between their neighbours
+                let at = i
+                    .checked_sub(1)
+                    .map(|prev| words[prev].end)
+                    .or_else(|| words.get(i).map(|next| next.start))
+                    .unwrap_or_default();
+                (at, at)
+            } else {
+                // Spread the new words over the time of the replaced ones
+                let first = k * replaced.len() / skip_tokens;
+                let last = ((k + 1) * replaced.len() / skip_tokens).max(first + 1) - 1;
+                (replaced[first].start, replaced[last].end)
+            };
+            aligned.push(TimedText {
+                start,
+                end,
+                text: token.to_string(),
+            });
+        }
+        i += skip_words;
+        j += skip_tokens;
+    }
+    aligned
+}
+
+fn timed_from_result(result: &TranscriptionResult) -> Vec<TimedText> {
+    result
+        .segments
+        .iter()
+        .flatten()
+        .map(|segment| TimedText {
+            start: segment.start,
+            end: segment.end,
+            text: segment.text.trim().to_string(),
+        })
+        .filter(|t| !t.text.is_empty())
+        .collect()
+}
+
+/// Post-processing that depends only on the settings, built once per transcription
+/// and applied to the text and each of its spans.
+struct TextPipeline {
+    filter: TranscriptionFilter,
+    punctuation: Vec<SpokenToken>,
+}
+
+impl TextPipeline {
+    fn new(settings: &AppSettings, language: &str) -> Self {
+        TextPipeline {
+            filter: TranscriptionFilter::new(
+                &settings.filler_words(language),
+                settings.collapse_stutters,
+            ),
+            punctuation: settings.spoken_punctuation_tokens(language),
+        }
+    }
+}
+
+/// The language of `text` when the user left the language on "auto". Translated
+/// output is always English, so nothing is guessed then.
+fn detect_auto_language(settings: &AppSettings, text: &str) -> Option<DetectedLanguage> {
+    if settings.selected_language != "auto" || settings.translate_to_english {
+        return None;
+    }
+    detect_language(text)
+}
+
+/// Parakeet models come as full precision or int8 ONNX files; load whichever is there.
+fn parakeet_params(path: &Path) -> Result<ParakeetModelParams> {
+    if path.join("encoder-model.int8.onnx").exists() {
+        Ok(ParakeetModelParams::int8())
+    } else if path.join("encoder-model.onnx").exists() {
+        Ok(ParakeetModelParams::fp32())
+    } else {
+        Err(anyhow::anyhow!(
+            "No encoder-model.onnx or encoder-model.int8.onnx in {:?}",
+            path
+        ))
+    }
+}
+
+/// The Moonshine variant of a model directory. The variants share their file names,
+/// so this reads the hidden size from the Hugging Face `config.json`. Returns `None`
+/// when there is no config, as for the bundled Base model.
+fn moonshine_variant(path: &Path) -> Result<Option<ModelVariant>> {
+    let config_path = path.join("config.json");
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&config_path)?)
+            .map_err(|e| anyhow::anyhow!("Invalid {:?}: {}", config_path, e))?;
+    match config.get("hidden_size").and_then(|size| size.as_u64()) {
+        Some(288) => Ok(Some(ModelVariant::Tiny)),
+        Some(416) => Ok(Some(ModelVariant::Base)),
+        Some(size) => Err(anyhow::anyhow!(
+            "Unsupported Moonshine model with hidden size {}, expected Tiny or Base",
+            size
+        )),
+        None => Err(anyhow::anyhow!("No hidden_size in {:?}", config_path)),
+    }
+}
+
+enum StreamCmd {
+    Chunk(Vec<f32>),
+    /// Decodes the remaining audio and replies with the joined result, or with
+    /// `None` when an earlier chunk failed and the stream is incomplete.
+    Finish(Vec<f32>, mpsc::Sender<Result<Option<Transcription>>>),
+}
+
+/// An in-progress streaming transcription. Speech chunks are decoded on a
+/// worker thread while recording continues; `streamed_samples` tracks how much
+/// of the recording has already been handed to the worker.
+struct StreamSession {
+    cmd_tx: mpsc::Sender<StreamCmd>,
+    streamed_samples: usize,
+}
+
 enum LoadedEngine {
     Whisper(WhisperEngine),
     Parakeet(ParakeetEngine),
     Moonshine(MoonshineEngine),
 }
 
+/// Engine for a binding that overrides the model. It is loaded next to the user's
+/// selected model, so using such a binding never changes the current model.
+#[derive(Default)]
+struct OverrideEngine {
+    model_id: Option<String>,
+    engine: Option<LoadedEngine>,
+    loading: bool,
+}
+
+impl LoadedEngine {
+    fn unload(&mut self) {
+        match self {
+            LoadedEngine::Whisper(e) => e.unload_model(),
+            LoadedEngine::Parakeet(e) => e.unload_model(),
+            LoadedEngine::Moonshine(e) => e.unload_model(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TranscriptionManager {
     engine: Arc<Mutex<Option<LoadedEngine>>>,
@@ -45,6 +352,9 @@ pub struct TranscriptionManager {
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    override_engine: Arc<Mutex<OverrideEngine>>,
+    override_condvar: Arc<Condvar>,
+    stream_session: Arc<Mutex<Option<StreamSession>>>,
 }
 
 impl TranscriptionManager {
@@ -64,6 +374,9 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            override_engine: Arc::new(Mutex::new(OverrideEngine::default())),
+            override_condvar: Arc::new(Condvar::new()),
+            stream_session: Arc::new(Mutex::new(None)),
         };
 
         // Start the idle watcher
@@ -134,6 +447,43 @@ impl TranscriptionManager {
         engine.is_some()
     }
 
+    /// Checks that the model at `path` loads with `engine_type`, without replacing the
+    /// loaded model. Used before importing user-supplied models.
+    pub fn validate_model(engine_type: &EngineType, path: &Path) -> Result<()> {
+        match engine_type {
+            EngineType::Whisper => {
+                let mut engine = WhisperEngine::new();
+                engine
+                    .load_model(path)
+                    .map_err(|e| anyhow::anyhow!("Not a valid Whisper model: {}", e))?;
+                engine.unload_model();
+            }
+            EngineType::Parakeet => {
+                let params = parakeet_params(path)
+                    .map_err(|e| anyhow::anyhow!("Not a valid Parakeet model: {}", e))?;
+                let mut engine = ParakeetEngine::new();
+                engine
+                    .load_model_with_params(path, params)
+                    .map_err(|e| anyhow::anyhow!("Not a valid Parakeet model: {}", e))?;
+                engine.unload_model();
+            }
+            EngineType::Moonshine => {
+                // Only the bundled model is assumed to be Base, imports have to say
+                let variant = moonshine_variant(path)?.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Can't tell which Moonshine model this is, add its config.json to the directory"
+                    )
+                })?;
+                let mut engine = MoonshineEngine::new();
+                engine
+                    .load_model_with_params(path, MoonshineModelParams::variant(variant))
+                    .map_err(|e| anyhow::anyhow!("Not a valid Moonshine model: {}", e))?;
+                engine.unload_model();
+            }
+        }
+        Ok(())
+    }
+
     pub fn unload_model(&self) -> Result<()> {
         let unload_start = std::time::Instant::now();
         debug!("Starting to unload model");
@@ -141,14 +491,17 @@ impl TranscriptionManager {
         {
             let mut engine = self.engine.lock().unwrap();
             if let Some(ref mut loaded_engine) = *engine {
-                match loaded_engine {
-                    LoadedEngine::Whisper(ref mut e) => e.unload_model(),
-                    LoadedEngine::Parakeet(ref mut e) => e.unload_model(),
-                    LoadedEngine::Moonshine(ref mut e) => e.unload_model(),
-                }
+                loaded_engine.unload();
             }
             *engine = None; // Drop the engine to free memory
         }
+        {
+            let mut slot = self.override_engine.lock().unwrap();
+            if let Some(ref mut override_engine) = slot.engine {
+                override_engine.unload();
+            }
+            slot.engine = None;
+        }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = None;
@@ -220,71 +573,18 @@ impl TranscriptionManager {
             return Err(anyhow::anyhow!(error_msg));
         }
 
-        let model_path = self.model_manager.get_model_path(model_id)?;
-
-        // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
-            }
-            EngineType::Moonshine => {
-                let mut engine = MoonshineEngine::new();
-                engine
-                    .load_model_with_params(
-                        &model_path,
-                        MoonshineModelParams::variant(ModelVariant::Base),
-                    )
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load moonshine model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Moonshine(engine)
-            }
-        };
+        let loaded_engine = self.create_engine(model_id, &model_info).map_err(|e| {
+            let _ = self.app_handle.emit(
+                "model-state-changed",
+                ModelStateEvent {
+                    event_type: "loading_failed".to_string(),
+                    model_id: Some(model_id.to_string()),
+                    model_name: Some(model_info.name.clone()),
+                    error: Some(e.to_string()),
+                },
+            );
+            e
+        })?;
 
         // Update the current engine and model ID
         {
@@ -316,6 +616,42 @@ impl TranscriptionManager {
         Ok(())
     }
 
+    /// Creates the engine for a downloaded model, without making it the loaded model.
+    fn create_engine(&self, model_id: &str, model_info: &ModelInfo) -> Result<LoadedEngine> {
+        let model_path = self.model_manager.get_model_path(model_id)?;
+
+        // Create appropriate engine based on model type
+        let loaded_engine = match model_info.engine_type {
+            EngineType::Whisper => {
+                let mut engine = WhisperEngine::new();
+                engine.load_model(&model_path).map_err(|e| {
+                    anyhow::anyhow!("Failed to load whisper model {}: {}", model_id, e)
+                })?;
+                LoadedEngine::Whisper(engine)
+            }
+            EngineType::Parakeet => {
+                let mut engine = ParakeetEngine::new();
+                engine
+                    .load_model_with_params(&model_path, parakeet_params(&model_path)?)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to load parakeet model {}: {}", model_id, e)
+                    })?;
+                LoadedEngine::Parakeet(engine)
+            }
+            EngineType::Moonshine => {
+                let variant = moonshine_variant(&model_path)?.unwrap_or(ModelVariant::Base);
+                let mut engine = MoonshineEngine::new();
+                engine
+                    .load_model_with_params(&model_path, MoonshineModelParams::variant(variant))
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to load moonshine model {}: {}", model_id, e)
+                    })?;
+                LoadedEngine::Moonshine(engine)
+            }
+        };
+        Ok(loaded_engine)
+    }
+
     /// Kicks off the model loading in a background thread if it's not already loaded
     pub fn initiate_model_load(&self) {
         let mut is_loading = self.is_loading.lock().unwrap();
@@ -336,13 +672,66 @@ impl TranscriptionManager {
         });
     }
 
+    /// Loads `model_id` in the background for a binding that overrides the model,
+    /// see [`transcribe_with_model`](Self::transcribe_with_model). The loaded model
+    /// stays the current one. A load that is already in flight finishes first.
+    pub fn initiate_model_load_for(&self, model_id: &str) {
+        if self.is_model_loaded() && self.get_current_model().as_deref() == Some(model_id) {
+            return;
+        }
+        {
+            let mut slot = self.override_engine.lock().unwrap();
+            if slot.model_id.as_deref() == Some(model_id) && (slot.loading || slot.engine.is_some())
+            {
+                return;
+            }
+            if let Some(ref mut previous) = slot.engine {
+                previous.unload();
+            }
+            *slot = OverrideEngine {
+                model_id: Some(model_id.to_string()),
+                engine: None,
+                loading: true,
+            };
+        }
+
+        let self_clone = self.clone();
+        let model_id = model_id.to_string();
+        thread::spawn(move || {
+            // Don't load two models at once
+            {
+                let mut is_loading = self_clone.is_loading.lock().unwrap();
+                while *is_loading {
+                    is_loading = self_clone.loading_condvar.wait(is_loading).unwrap();
+                }
+            }
+
+            let engine = self_clone
+                .model_manager
+                .get_model_info(&model_id)
+                .filter(|info| info.is_downloaded)
+                .ok_or_else(|| anyhow::anyhow!("Model not downloaded: {}", model_id))
+                .and_then(|info| self_clone.create_engine(&model_id, &info));
+            if let Err(e) = &engine {
+                error!("Failed to load model {}: {}", model_id, e);
+            }
+
+            let mut slot = self_clone.override_engine.lock().unwrap();
+            // Another binding may have asked for a different model meanwhile
+            if slot.model_id.as_deref() == Some(model_id.as_str()) {
+                slot.engine = engine.ok();
+                slot.loading = false;
+                self_clone.override_condvar.notify_all();
+            }
+        });
+    }
+
     pub fn get_current_model(&self) -> Option<String> {
         let current_model = self.current_model_id.lock().unwrap();
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
-        // Update last activity timestamp
+    fn touch_activity(&self) {
         self.last_activity.store(
             SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
@@ -350,98 +739,235 @@ impl TranscriptionManager {
                 .as_millis() as u64,
             Ordering::Relaxed,
         );
+    }
 
-        let st = std::time::Instant::now();
-
-        debug!("Audio vector length: {}", audio.len());
-
-        if audio.is_empty() {
-            debug!("Empty audio vector");
-            self.maybe_unload_immediately("empty audio");
-            return Ok(String::new());
+    /// Blocks until any in-flight model load has finished, then checks that an
+    /// engine is available.
+    fn wait_for_model(&self) -> Result<()> {
+        // If the model is loading, wait for it to complete.
+        let mut is_loading = self.is_loading.lock().unwrap();
+        while *is_loading {
+            is_loading = self.loading_condvar.wait(is_loading).unwrap();
         }
 
-        // Check if model is loaded, if not try to load it
-        {
-            // If the model is loading, wait for it to complete.
-            let mut is_loading = self.is_loading.lock().unwrap();
-            while *is_loading {
-                is_loading = self.loading_condvar.wait(is_loading).unwrap();
-            }
-
-            let engine_guard = self.engine.lock().unwrap();
-            if engine_guard.is_none() {
-                return Err(anyhow::anyhow!("Model is not loaded for transcription."));
-            }
+        let engine_guard = self.engine.lock().unwrap();
+        if engine_guard.is_none() {
+            return Err(anyhow::anyhow!("Model is not loaded for transcription."));
         }
+        Ok(())
+    }
 
-        // Get current settings for configuration
-        let settings = get_settings(&self.app_handle);
+    /// Runs the loaded engine on the given audio and returns the raw text and
+    /// timestamps, without custom word correction or filler filtering.
+    fn run_engine(&self, audio: Vec<f32>, settings: &AppSettings) -> Result<Transcription> {
+        let mut engine_guard = self.engine.lock().unwrap();
+        let engine = engine_guard.as_mut().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model failed to load after auto-load attempt. Please check your model settings."
+            )
+        })?;
+        Self::run_loaded_engine(engine, audio, settings)
+    }
 
-        // Perform transcription with the appropriate engine
-        let result = {
-            let mut engine_guard = self.engine.lock().unwrap();
-            let engine = engine_guard.as_mut().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Model failed to load after auto-load attempt. Please check your model settings."
-                )
-            })?;
-
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    // Normalize language code for Whisper
-                    // Convert zh-Hans and zh-Hant to zh since Whisper uses ISO 639-1 codes
-                    let whisper_language = if settings.selected_language == "auto" {
-                        None
+    fn run_loaded_engine(
+        engine: &mut LoadedEngine,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+    ) -> Result<Transcription> {
+        let duration = audio.len() as f32 / WHISPER_SAMPLE_RATE as f32;
+        let (result, words) = match engine {
+            LoadedEngine::Whisper(whisper_engine) => {
+                // Normalize language code for Whisper
+                // Convert zh-Hans and zh-Hant to zh since Whisper uses ISO 639-1 codes
+                let whisper_language = if settings.selected_language == "auto" {
+                    None
+                } else {
+                    let normalized = if settings.selected_language == "zh-Hans"
+                        || settings.selected_language == "zh-Hant"
+                    {
+                        "zh".to_string()
                     } else {
-                        let normalized = if settings.selected_language == "zh-Hans"
-                            || settings.selected_language == "zh-Hant"
-                        {
-                            "zh".to_string()
-                        } else {
-                            settings.selected_language.clone()
-                        };
-                        Some(normalized)
+                        settings.selected_language.clone()
                     };
+                    Some(normalized)
+                };
 
-                    let params = WhisperInferenceParams {
-                        language: whisper_language,
-                        translate: settings.translate_to_english,
-                        ..Default::default()
-                    };
+                let params = WhisperInferenceParams {
+                    language: whisper_language,
+                    translate: settings.translate_to_english,
+                    ..Default::default()
+                };
 
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
-                }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                        ..Default::default()
-                    };
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
-                }
-                LoadedEngine::Moonshine(moonshine_engine) => moonshine_engine
+                let result = whisper_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+                (result, None)
+            }
+            LoadedEngine::Parakeet(parakeet_engine) => {
+                // Word granularity gives us word timestamps; segments are rebuilt from them
+                let params = ParakeetInferenceParams {
+                    timestamp_granularity: TimestampGranularity::Word,
+                    ..Default::default()
+                };
+                let result = parakeet_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?;
+                let words = timed_from_result(&result);
+                (result, Some(words))
+            }
+            LoadedEngine::Moonshine(moonshine_engine) => {
+                let result = moonshine_engine
                     .transcribe_samples(audio, None)
-                    .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e))?,
+                    .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e))?;
+                (result, None)
             }
         };
 
+        let mut segments = match &words {
+            Some(words) => segments_from_words(words),
+            None => timed_from_result(&result),
+        };
+        // Engines without timestamps get a single segment spanning the whole clip
+        if segments.is_empty() && !result.text.trim().is_empty() {
+            segments.push(TimedText {
+                start: 0.0,
+                end: duration,
+                text: result.text.trim().to_string(),
+            });
+        }
+
+        Ok(Transcription {
+            text: result.text,
+            segments,
+            words,
+            language: None,
+            raw_text: String::new(),
+        })
+    }
+
+    /// Applies [`finalize_text`](Self::finalize_text) to the full text and to every
+    /// segment, dropping segments that are filtered out entirely. Words are finalized
+    /// together so multi-word corrections work, then mapped back onto their timings.
+    /// Replacement rules only run once, on the full text.
+    /// In "auto" mode the language is detected first, so the language-specific steps
+    /// use the language that was actually spoken.
+    fn finalize(&self, raw: Transcription, settings: &AppSettings) -> Transcription {
+        let language = raw
+            .language
+            .clone()
+            .or_else(|| detect_auto_language(settings, &raw.text));
+        if let Some(language) = &language {
+            debug!("Guessed language '{}' from the text", language.code);
+        }
+        let code = language
+            .as_ref()
+            .map_or(settings.selected_language.as_str(), |l| l.code.as_str());
+
+        // Compile the rules and filters once for the text and all of its spans
+        let pipeline = TextPipeline::new(settings, code);
+        let finalize_spans = |spans: Vec<TimedText>| -> Vec<TimedText> {
+            spans
+                .into_iter()
+                .filter_map(|span| {
+                    let text = self.finalize_text_with(&span.text, settings, &pipeline, None);
+                    (!text.is_empty()).then_some(TimedText { text, ..span })
+                })
+                .collect()
+        };
+
+        Transcription {
+            raw_text: raw.text.trim().to_string(),
+            text: self.finalize_text_with(
+                &raw.text,
+                settings,
+                &pipeline,
+                Some(&CompiledReplacements::new(&settings.replacement_rules)),
+            ),
+            segments: finalize_spans(raw.segments),
+            words: raw.words.map(|words| {
+                let joined = words
+                    .iter()
+                    .map(|w| w.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                align_words(
+                    &words,
+                    &self.finalize_text_with(&joined, settings, &pipeline, None),
+                )
+            }),
+            language,
+        }
+    }
+
+    /// Applies custom word correction, filler/hallucination filtering and spoken
+    /// punctuation to a streaming preview. Replacement rules wait for the final text.
+    fn finalize_text(&self, text: &str, settings: &AppSettings) -> String {
+        let language = detect_auto_language(settings, text);
+        let code = language
+            .as_ref()
+            .map_or(settings.selected_language.as_str(), |l| l.code.as_str());
+        self.finalize_text_with(text, settings, &TextPipeline::new(settings, code), None)
+    }
+
+    fn finalize_text_with(
+        &self,
+        text: &str,
+        settings: &AppSettings,
+        pipeline: &TextPipeline,
+        replacements: Option<&CompiledReplacements>,
+    ) -> String {
         // Apply word correction if custom words are configured
         let corrected_result = if !settings.custom_words.is_empty() {
             apply_custom_words(
-                &result.text,
+                text,
                 &settings.custom_words,
                 settings.word_correction_threshold,
             )
         } else {
-            result.text
+            text.to_string()
         };
 
-        // Filter out filler words and hallucinations
-        let filtered_result = filter_transcription_output(&corrected_result);
+        // Deterministic find/replace rules run after the fuzzy custom words
+        let replaced = match replacements {
+            Some(replacements) if !replacements.is_empty() => replacements.apply(&corrected_result),
+            _ => corrected_result,
+        };
+
+        // Filter out the language's filler words and, optionally, stutters
+        let filtered = pipeline.filter.apply(&replaced);
+
+        // Turn spoken "comma", "new line", ... into symbols and line breaks
+        apply_spoken_punctuation(&filtered, &pipeline.punctuation)
+    }
+
+    /// Transcribes with an explicit settings snapshot, so callers can override
+    /// options such as the language for a single run without persisting them.
+    /// Returns the text along with segment and word timestamps.
+    pub fn transcribe_with_settings(
+        &self,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+    ) -> Result<Transcription> {
+        // Update last activity timestamp
+        self.touch_activity();
+
+        let st = std::time::Instant::now();
+
+        debug!("Audio vector length: {}", audio.len());
+
+        if audio.is_empty() {
+            debug!("Empty audio vector");
+            self.maybe_unload_immediately("empty audio");
+            return Ok(Transcription::default());
+        }
+
+        // Check if model is loaded, if not try to load it
+        self.wait_for_model()?;
+
+        // Perform transcription with the appropriate engine
+        let raw = self.run_engine(audio, settings)?;
+
+        let filtered_result = self.finalize(raw, settings);
 
         let et = std::time::Instant::now();
         let translation_note = if settings.translate_to_english {
@@ -457,16 +983,262 @@ impl TranscriptionManager {
 
         let final_result = filtered_result;
 
-        if final_result.is_empty() {
+        if final_result.text.is_empty() {
             info!("Transcription result is empty");
         } else {
-            info!("Transcription result: {}", final_result);
+            info!(
+                "Transcription result ({} segments): {}",
+                final_result.segments.len(),
+                final_result.text
+            );
         }
 
         self.maybe_unload_immediately("transcription");
 
         Ok(final_result)
     }
+
+    /// Transcribes with `model_id` without replacing the loaded model. A binding's
+    /// override engine is used once it has loaded; any other model gets a separate
+    /// engine for this run only, so dictation keeps using the user's model meanwhile.
+    pub fn transcribe_with_model(
+        &self,
+        model_id: &str,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+    ) -> Result<Transcription> {
+        if self.is_model_loaded() && self.get_current_model().as_deref() == Some(model_id) {
+            return self.transcribe_with_settings(audio, settings);
+        }
+
+        {
+            let mut slot = self.override_engine.lock().unwrap();
+            while slot.loading && slot.model_id.as_deref() == Some(model_id) {
+                slot = self.override_condvar.wait(slot).unwrap();
+            }
+            if slot.model_id.as_deref() == Some(model_id) {
+                if let Some(engine) = slot.engine.as_mut() {
+                    self.touch_activity();
+                    if audio.is_empty() {
+                        return Ok(Transcription::default());
+                    }
+                    let raw = Self::run_loaded_engine(engine, audio, settings)?;
+                    drop(slot);
+                    let result = self.finalize(raw, settings);
+                    self.maybe_unload_immediately("transcription");
+                    return Ok(result);
+                }
+            }
+        }
+
+        let model_info = self
+            .model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if !model_info.is_downloaded {
+            return Err(anyhow::anyhow!("Model not downloaded: {}", model_id));
+        }
+        if audio.is_empty() {
+            return Ok(Transcription::default());
+        }
+
+        let st = std::time::Instant::now();
+        let mut engine = self.create_engine(model_id, &model_info)?;
+        let raw = Self::run_loaded_engine(&mut engine, audio, settings);
+        engine.unload();
+        let result = self.finalize(raw?, settings);
+
+        info!(
+            "Transcription with {} completed in {}ms",
+            model_id,
+            st.elapsed().as_millis()
+        );
+        Ok(result)
+    }
+
+    /* ---------- streaming --------------------------------------------------- */
+
+    pub fn is_streaming(&self) -> bool {
+        self.stream_session.lock().unwrap().is_some()
+    }
+
+    /// Starts a streaming session. Speech chunks pushed with
+    /// [`push_stream_chunk`](Self::push_stream_chunk) are decoded in the
+    /// background and a `partial-transcription` event is emitted with the text
+    /// of each committed chunk. Call this before recording starts so no chunk
+    /// arrives without a session.
+    pub fn start_streaming(&self) {
+        let (cmd_tx, cmd_rx) = mpsc::channel::<StreamCmd>();
+
+        {
+            let mut session = self.stream_session.lock().unwrap();
+            if session.is_some() {
+                debug!("Replacing previous streaming session");
+            }
+            *session = Some(StreamSession {
+                cmd_tx,
+                streamed_samples: 0,
+            });
+        }
+
+        // Use the managed instance so the worker does not own a standalone clone
+        let tm = Arc::clone(&self.app_handle.state::<Arc<TranscriptionManager>>());
+        thread::spawn(move || {
+            let settings = get_settings(&tm.app_handle);
+            let mut committed: Vec<Transcription> = Vec::new();
+            // Samples handed to the worker so far, used to offset chunk timestamps
+            let mut offset_samples = 0usize;
+            // Set once a chunk fails; its speech is missing from `committed`
+            let mut chunk_failed = false;
+
+            for cmd in cmd_rx {
+                match cmd {
+                    StreamCmd::Chunk(chunk) => {
+                        if chunk_failed {
+                            continue;
+                        }
+                        let chunk_len = chunk.len();
+                        let offset = offset_samples;
+                        offset_samples += chunk_len;
+                        match tm.decode_chunk(chunk, offset, &settings) {
+                            Ok(part) => {
+                                if part.text.is_empty() {
+                                    continue;
+                                }
+                                let text = tm.finalize_text(&part.text, &settings);
+                                let chunk_index = committed.len();
+                                committed.push(part);
+                                debug!(
+                                    "Committed streaming chunk {} ({} samples)",
+                                    chunk_index, chunk_len
+                                );
+                                let _ = tm.app_handle.emit(
+                                    "partial-transcription",
+                                    PartialTranscriptionEvent { text, chunk_index },
+                                );
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "Failed to transcribe streaming chunk, the full recording will be transcribed instead: {}",
+                                    e
+                                );
+                                chunk_failed = true;
+                            }
+                        }
+                    }
+                    StreamCmd::Finish(_, reply_tx) if chunk_failed => {
+                        let _ = reply_tx.send(Ok(None));
+                        break;
+                    }
+                    StreamCmd::Finish(tail, reply_tx) => {
+                        if !tail.is_empty() {
+                            match tm.decode_chunk(tail, offset_samples, &settings) {
+                                Ok(part) if !part.text.is_empty() => committed.push(part),
+                                Ok(_) => {}
+                                Err(e) => {
+                                    let _ = reply_tx.send(Err(e));
+                                    break;
+                                }
+                            }
+                        }
+                        let result = tm.finalize(Transcription::join(committed), &settings);
+                        let _ = reply_tx.send(Ok(Some(result)));
+                        break;
+                    }
+                }
+            }
+            debug!("Streaming transcription worker finished");
+        });
+    }
+
+    /// Decodes one chunk that starts `offset` samples into the recording.
+    fn decode_chunk(
+        &self,
+        chunk: Vec<f32>,
+        offset: usize,
+        settings: &AppSettings,
+    ) -> Result<Transcription> {
+        self.touch_activity();
+        self.wait_for_model()?;
+        let mut part = self.run_engine(chunk, settings)?;
+        part.text = part.text.trim().to_string();
+        Ok(part.offset_by(offset as f32 / WHISPER_SAMPLE_RATE as f32))
+    }
+
+    /// Hands a speech chunk to the active streaming session. Without a session the
+    /// chunk is not decoded here and `finish_streaming` transcribes the whole
+    /// recording instead.
+    pub fn push_stream_chunk(&self, chunk: Vec<f32>) {
+        let mut session = self.stream_session.lock().unwrap();
+        match session.as_mut() {
+            Some(session) => {
+                session.streamed_samples += chunk.len();
+                if session.cmd_tx.send(StreamCmd::Chunk(chunk)).is_err() {
+                    warn!("Streaming worker is no longer running");
+                }
+            }
+            None => debug!("Dropping speech chunk, no streaming session is active"),
+        }
+    }
+
+    /// Ends the streaming session and returns the final transcription. Only the
+    /// audio recorded after the last committed chunk is decoded here. Falls back
+    /// to a regular transcription if no session is active or a chunk failed to
+    /// decode.
+    pub fn finish_streaming(&self, samples: &[f32]) -> Result<Transcription> {
+        let session = self.stream_session.lock().unwrap().take();
+        let Some(session) = session else {
+            let settings = get_settings(&self.app_handle);
+            return self.transcribe_with_settings(samples.to_vec(), &settings);
+        };
+
+        let st = std::time::Instant::now();
+        let tail = samples
+            .get(session.streamed_samples..)
+            .unwrap_or_default()
+            .to_vec();
+        debug!(
+            "Finishing streaming transcription: {} samples already decoded, {} remaining",
+            session.streamed_samples,
+            tail.len()
+        );
+
+        let (reply_tx, reply_rx) = mpsc::channel();
+        session
+            .cmd_tx
+            .send(StreamCmd::Finish(tail, reply_tx))
+            .map_err(|_| anyhow::anyhow!("Streaming worker is no longer running"))?;
+        let result = match reply_rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("Streaming worker exited without a result"))?
+        {
+            Ok(Some(transcription)) => Ok(transcription),
+            Ok(None) => {
+                let settings = get_settings(&self.app_handle);
+                return self.transcribe_with_settings(samples.to_vec(), &settings);
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Ok(transcription) = &result {
+            info!(
+                "Streaming transcription finalized in {}ms: {}",
+                st.elapsed().as_millis(),
+                transcription.text
+            );
+        }
+
+        self.maybe_unload_immediately("transcription");
+
+        result
+    }
+
+    /// Drops the active streaming session without producing a result.
+    pub fn cancel_streaming(&self) {
+        if self.stream_session.lock().unwrap().take().is_some() {
+            debug!("Streaming transcription cancelled");
+        }
+    }
 }
 
 impl Drop for TranscriptionManager {
@@ -486,3 +1258,52 @@ impl Drop for TranscriptionManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timed(words: &[(&str, f32, f32)]) -> Vec<TimedText> {
+        words
+            .iter()
+            .map(|&(text, start, end)| TimedText {
+                start,
+                end,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn aligned_words_keep_their_timings() {
+        let words = timed(&[("hello", 0.0, 0.5), ("um", 0.5, 0.8), ("world", 0.8, 1.2)]);
+        assert_eq!(
+            align_words(&words, "Hello, world."),
+            timed(&[("Hello,", 0.0, 0.5), ("world.", 0.8, 1.2)])
+        );
+    }
+
+    #[test]
+    fn multi_word_changes_span_the_replaced_words() {
+        let words = timed(&[
+            ("chat", 0.0, 0.3),
+            ("gpt", 0.3, 0.6),
+            ("is", 0.6, 0.8),
+            ("great", 0.8, 1.0),
+            ("btw", 1.0, 1.5),
+            ("thanks", 1.5, 2.0),
+        ]);
+        assert_eq!(
+            align_words(&words, "ChatGPT is great by the way, thanks"),
+            timed(&[
+                ("ChatGPT", 0.0, 0.6),
+                ("is", 0.6, 0.8),
+                ("great", 0.8, 1.0),
+                ("by", 1.0, 1.5),
+                ("the", 1.0, 1.5),
+                ("way,", 1.0, 1.5),
+                ("thanks", 1.5, 2.0),
+            ])
+        );
+    }
+}
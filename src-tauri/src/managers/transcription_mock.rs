@@ -2,9 +2,13 @@
 // This file is copied over transcription.rs during CI tests.
 // Existing tests don't exercise transcription, so this is safe.
 
-use crate::managers::model::ModelManager;
+use crate::audio_toolkit::DetectedLanguage;
+use crate::managers::model::{EngineType, ModelManager};
+use crate::settings::AppSettings;
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
 use std::sync::Arc;
 use tauri::AppHandle;
 
@@ -16,6 +20,32 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct TimedText {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct Transcription {
+    pub text: String,
+    pub segments: Vec<TimedText>,
+    pub words: Option<Vec<TimedText>>,
+    #[serde(default)]
+    pub language: Option<DetectedLanguage>,
+    #[serde(skip)]
+    pub raw_text: String,
+}
+
+impl Transcription {
+    pub fn language_or<'a>(&'a self, selected: &'a str) -> &'a str {
+        self.language
+            .as_ref()
+            .map_or(selected, |language| language.code.as_str())
+    }
+}
+
 #[derive(Clone)]
 pub struct TranscriptionManager {
     #[allow(dead_code)]
@@ -33,6 +63,10 @@ impl TranscriptionManager {
         false
     }
 
+    pub fn validate_model(_engine_type: &EngineType, _path: &Path) -> Result<()> {
+        Ok(())
+    }
+
     pub fn unload_model(&self) -> Result<()> {
         Ok(())
     }
@@ -45,11 +79,40 @@ impl TranscriptionManager {
 
     pub fn initiate_model_load(&self) {}
 
+    pub fn initiate_model_load_for(&self, _model_id: &str) {}
+
     pub fn get_current_model(&self) -> Option<String> {
         None
     }
 
-    pub fn transcribe(&self, _audio: Vec<f32>) -> Result<String> {
-        Ok(String::new())
+    pub fn transcribe_with_settings(
+        &self,
+        _audio: Vec<f32>,
+        _settings: &AppSettings,
+    ) -> Result<Transcription> {
+        Ok(Transcription::default())
     }
+
+    pub fn transcribe_with_model(
+        &self,
+        _model_id: &str,
+        _audio: Vec<f32>,
+        _settings: &AppSettings,
+    ) -> Result<Transcription> {
+        Ok(Transcription::default())
+    }
+
+    pub fn is_streaming(&self) -> bool {
+        false
+    }
+
+    pub fn start_streaming(&self) {}
+
+    pub fn push_stream_chunk(&self, _chunk: Vec<f32>) {}
+
+    pub fn finish_streaming(&self, _samples: &[f32]) -> Result<Transcription> {
+        Ok(Transcription::default())
+    }
+
+    pub fn cancel_streaming(&self) {}
 }
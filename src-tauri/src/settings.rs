@@ -1,3 +1,5 @@
+use crate::audio_toolkit::{builtin_filler_words, builtin_tokens, ReplacementRule, SpokenToken};
+use crate::helpers::active_app::ActiveApp;
 use log::{debug, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -83,13 +85,104 @@ pub struct ShortcutBinding {
     pub description: String,
     pub default_binding: String,
     pub current_binding: String,
+    /// Post-process with this prompt instead of the selected one
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+    /// Transcribe with this model instead of the selected one
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct LLMPrompt {
     pub id: String,
     pub name: String,
+    /// User message template, `${output}` is replaced with the transcription
     pub prompt: String,
+    /// Sent as a separate system message when set
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Few-shot examples, sent as user/assistant turns before the transcription
+    #[serde(default)]
+    pub examples: Vec<PromptExample>,
+    /// Extra `${name}` variables substituted in the system prompt and template
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct PromptExample {
+    pub input: String,
+    pub output: String,
+}
+
+/// Overrides applied when dictating into a matching application.
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct AppProfile {
+    pub id: String,
+    pub name: String,
+    /// Process names, window classes or bundle ids this profile applies to
+    pub app_matchers: Vec<String>,
+    /// Prompt used when the binding post-processes
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+    #[serde(default)]
+    pub paste_method: Option<PasteMethod>,
+    #[serde(default)]
+    pub append_trailing_space: Option<bool>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Text pasted in place of its spoken trigger, e.g. "insert my signature"
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct Snippet {
+    pub id: String,
+    pub trigger: String,
+    pub content: String,
+}
+
+/// User changes to the built-in filler words of one language
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Type)]
+pub struct FillerWordOverrides {
+    #[serde(default)]
+    pub added: Vec<String>,
+    /// Built-in filler words that should be kept in the text
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+/// What a voice command does when its phrase is spoken
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VoiceCommandAction {
+    /// Space-separated key combos, e.g. `ctrl+a` or `enter enter`
+    Keys { keys: String },
+    /// Run through the platform shell without waiting for it to finish
+    Shell { command: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct VoiceCommand {
+    pub id: String,
+    pub phrase: String,
+    pub action: VoiceCommandAction,
+}
+
+/// Request/response format spoken by a post-processing provider
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderProtocol {
+    /// OpenAI-compatible `/chat/completions`
+    OpenaiChat,
+    /// Anthropic `/messages`
+    AnthropicMessages,
+    /// Ollama's native `/api/chat`
+    OllamaChat,
+    /// Prompt-only `/completions`, for servers without chat templates
+    TextCompletion,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -101,6 +194,19 @@ pub struct PostProcessProvider {
     pub allow_base_url_edit: bool,
     #[serde(default)]
     pub models_endpoint: Option<String>,
+    #[serde(default)]
+    pub protocol: ProviderProtocol,
+    /// Per-attempt request timeout; streamed responses may take longer as long as data keeps arriving.
+    /// `None` waits as long as the provider takes.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Extra attempts after a connection error, 429 or 5xx response
+    #[serde(default = "default_provider_max_retries")]
+    pub max_retries: u32,
+    /// Also retry requests that hit `timeout_secs`. Off by default, since a slow
+    /// provider usually stays slow and every attempt waits for the full timeout.
+    #[serde(default)]
+    pub retry_on_timeout: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -132,6 +238,8 @@ pub enum PasteMethod {
     None,
     ShiftInsert,
     CtrlShiftV,
+    /// Like `Direct`, but post-processed text is typed as the LLM streams it
+    DirectStreaming,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -185,6 +293,12 @@ impl Default for PasteMethod {
     }
 }
 
+impl Default for ProviderProtocol {
+    fn default() -> Self {
+        ProviderProtocol::OpenaiChat
+    }
+}
+
 impl Default for ClipboardHandling {
     fn default() -> Self {
         ClipboardHandling::DontModify
@@ -259,6 +373,21 @@ pub struct AppSettings {
     pub update_checks_enabled: bool,
     #[serde(default = "default_model")]
     pub selected_model: String,
+    /// Model catalog merged over the bundled one, fetched at startup
+    #[serde(default)]
+    pub model_catalog_url: Option<String>,
+    /// Base URL that model downloads are fetched from instead of their catalog host
+    #[serde(default)]
+    pub model_mirror_url: Option<String>,
+    /// HTTP(S) proxy for model and catalog downloads, overriding the system proxy
+    #[serde(default)]
+    pub model_download_proxy: Option<String>,
+    /// How many queued model downloads run at the same time
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: u32,
+    /// Combined speed limit for model downloads, in bytes per second
+    #[serde(default)]
+    pub download_speed_limit: Option<u64>,
     #[serde(default = "default_always_on_microphone")]
     pub always_on_microphone: bool,
     #[serde(default)]
@@ -280,9 +409,34 @@ pub struct AppSettings {
     #[serde(default)]
     pub custom_words: Vec<String>,
     #[serde(default)]
+    pub snippets: Vec<Snippet>,
+    #[serde(default)]
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
+    /// Find/replace rules applied in order after custom words
+    #[serde(default)]
+    pub replacement_rules: Vec<ReplacementRule>,
+    #[serde(default = "default_filler_word_filter_enabled")]
+    pub filler_word_filter_enabled: bool,
+    /// Keyed by base language code, e.g. `en` or `it`
+    #[serde(default)]
+    pub filler_word_overrides: HashMap<String, FillerWordOverrides>,
+    /// Collapse stutters like "I I I" into a single word
+    #[serde(default = "default_collapse_stutters")]
+    pub collapse_stutters: bool,
+    /// Write spoken tokens like "comma" or "open paren" as symbols
+    #[serde(default)]
+    pub spoken_punctuation_enabled: bool,
+    /// Also handle layout tokens like "new line" and "bullet point"
+    #[serde(default = "default_spoken_formatting_enabled")]
+    pub spoken_formatting_enabled: bool,
+    /// User tokens, matched before the built-in vocabulary of the language
+    #[serde(default)]
+    pub spoken_punctuation_custom: Vec<SpokenToken>,
+    /// Built-in phrases the user switched off
+    #[serde(default)]
+    pub spoken_punctuation_disabled: Vec<String>,
     #[serde(default = "default_history_limit")]
     pub history_limit: usize,
     #[serde(default = "default_recording_retention_period")]
@@ -297,6 +451,9 @@ pub struct AppSettings {
     pub post_process_provider_id: String,
     #[serde(default = "default_post_process_providers")]
     pub post_process_providers: Vec<PostProcessProvider>,
+    /// Providers tried in order when the active one fails
+    #[serde(default)]
+    pub post_process_fallback_provider_ids: Vec<String>,
     #[serde(default = "default_post_process_api_keys")]
     pub post_process_api_keys: HashMap<String, String>,
     #[serde(default = "default_post_process_models")]
@@ -305,6 +462,19 @@ pub struct AppSettings {
     pub post_process_prompts: Vec<LLMPrompt>,
     #[serde(default)]
     pub post_process_selected_prompt_id: Option<String>,
+    /// Prompt ids keyed by language code, used instead of the selected prompt when the
+    /// transcription is in that language
+    #[serde(default)]
+    pub post_process_language_prompts: HashMap<String, String>,
+    /// Checked in order, the first profile matching the frontmost app wins
+    #[serde(default)]
+    pub app_profiles: Vec<AppProfile>,
+    #[serde(default)]
+    pub voice_commands_enabled: bool,
+    #[serde(default = "default_voice_commands")]
+    pub voice_commands: Vec<VoiceCommand>,
+    #[serde(default = "default_voice_command_threshold")]
+    pub voice_command_threshold: f64,
     #[serde(default)]
     pub mute_while_recording: bool,
     #[serde(default)]
@@ -317,12 +487,20 @@ pub struct AppSettings {
     pub keyboard_implementation: KeyboardImplementation,
     #[serde(default = "default_paste_delay_ms")]
     pub paste_delay_ms: u64,
+    #[serde(default)]
+    pub streaming_transcription: bool,
+    #[serde(default)]
+    pub control_socket_enabled: bool,
 }
 
 fn default_model() -> String {
     "".to_string()
 }
 
+fn default_max_concurrent_downloads() -> u32 {
+    2
+}
+
 fn default_always_on_microphone() -> bool {
     false
 }
@@ -366,6 +544,44 @@ fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+fn default_filler_word_filter_enabled() -> bool {
+    true
+}
+
+fn default_collapse_stutters() -> bool {
+    true
+}
+
+fn default_spoken_formatting_enabled() -> bool {
+    true
+}
+
+fn default_voice_command_threshold() -> f64 {
+    0.2
+}
+
+fn default_voice_commands() -> Vec<VoiceCommand> {
+    #[cfg(target_os = "macos")]
+    let select_all = "cmd+a";
+    #[cfg(not(target_os = "macos"))]
+    let select_all = "ctrl+a";
+
+    [
+        ("default_new_paragraph", "new paragraph", "enter enter"),
+        ("default_select_all", "select all", select_all),
+        ("default_send_message", "send message", "enter"),
+    ]
+    .into_iter()
+    .map(|(id, phrase, keys)| VoiceCommand {
+        id: id.to_string(),
+        phrase: phrase.to_string(),
+        action: VoiceCommandAction::Keys {
+            keys: keys.to_string(),
+        },
+    })
+    .collect()
+}
+
 fn default_paste_delay_ms() -> u64 {
     60
 }
@@ -400,6 +616,10 @@ fn default_post_process_provider_id() -> String {
     "openai".to_string()
 }
 
+pub(crate) fn default_provider_max_retries() -> u32 {
+    2
+}
+
 fn default_post_process_providers() -> Vec<PostProcessProvider> {
     let mut providers = vec![
         PostProcessProvider {
@@ -408,6 +628,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.openai.com/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            protocol: ProviderProtocol::OpenaiChat,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         },
         PostProcessProvider {
             id: "openrouter".to_string(),
@@ -415,6 +639,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://openrouter.ai/api/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            protocol: ProviderProtocol::OpenaiChat,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         },
         PostProcessProvider {
             id: "anthropic".to_string(),
@@ -422,6 +650,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.anthropic.com/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            protocol: ProviderProtocol::AnthropicMessages,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         },
         PostProcessProvider {
             id: "groq".to_string(),
@@ -429,6 +661,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.groq.com/openai/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            protocol: ProviderProtocol::OpenaiChat,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         },
         PostProcessProvider {
             id: "cerebras".to_string(),
@@ -436,6 +672,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.cerebras.ai/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            protocol: ProviderProtocol::OpenaiChat,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         },
     ];
 
@@ -451,6 +691,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "apple-intelligence://local".to_string(),
             allow_base_url_edit: false,
             models_endpoint: None,
+            protocol: ProviderProtocol::OpenaiChat,
+            timeout_secs: None,
+            max_retries: default_provider_max_retries(),
+            retry_on_timeout: false,
         });
     }
 
@@ -461,6 +705,10 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
         base_url: "http://localhost:11434/v1".to_string(),
         allow_base_url_edit: true,
         models_endpoint: Some("/models".to_string()),
+        protocol: ProviderProtocol::OpenaiChat,
+        timeout_secs: None,
+        max_retries: default_provider_max_retries(),
+        retry_on_timeout: false,
     });
 
     providers
@@ -497,6 +745,9 @@ fn default_post_process_prompts() -> Vec<LLMPrompt> {
         id: "default_improve_transcriptions".to_string(),
         name: "Improve Transcriptions".to_string(),
         prompt: include_str!("../post-processing-prompt.md").to_string(),
+        system_prompt: None,
+        examples: Vec::new(),
+        variables: HashMap::new(),
     }]
 }
 
@@ -512,6 +763,21 @@ fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
             changed = true;
         }
 
+        // Built-in providers always speak their native protocol; older settings
+        // predate the field and deserialize as OpenAI chat
+        if !provider.allow_base_url_edit {
+            if let Some(existing) = settings
+                .post_process_providers
+                .iter_mut()
+                .find(|existing| {
+                    existing.id == provider.id && existing.protocol != provider.protocol
+                })
+            {
+                existing.protocol = provider.protocol;
+                changed = true;
+            }
+        }
+
         if !settings.post_process_api_keys.contains_key(&provider.id) {
             settings
                 .post_process_api_keys
@@ -566,6 +832,9 @@ pub fn get_default_settings() -> AppSettings {
             description: "Converts your speech into text.".to_string(),
             default_binding: default_shortcut.to_string(),
             current_binding: default_shortcut.to_string(),
+            prompt_id: None,
+            model_id: None,
+            language: None,
         },
     );
     #[cfg(target_os = "windows")]
@@ -586,6 +855,28 @@ pub fn get_default_settings() -> AppSettings {
                 .to_string(),
             default_binding: default_post_process_shortcut.to_string(),
             current_binding: default_post_process_shortcut.to_string(),
+            prompt_id: None,
+            model_id: None,
+            language: None,
+        },
+    );
+    #[cfg(target_os = "macos")]
+    let default_voice_command_shortcut = "ctrl+option+space";
+    #[cfg(not(target_os = "macos"))]
+    let default_voice_command_shortcut = "ctrl+alt+space";
+
+    bindings.insert(
+        "voice_command".to_string(),
+        ShortcutBinding {
+            id: "voice_command".to_string(),
+            name: "Voice Command".to_string(),
+            description: "Runs the spoken voice command, or pastes the text if none matches."
+                .to_string(),
+            default_binding: default_voice_command_shortcut.to_string(),
+            current_binding: default_voice_command_shortcut.to_string(),
+            prompt_id: None,
+            model_id: None,
+            language: None,
         },
     );
     bindings.insert(
@@ -596,6 +887,9 @@ pub fn get_default_settings() -> AppSettings {
             description: "Cancels the current recording.".to_string(),
             default_binding: "escape".to_string(),
             current_binding: "escape".to_string(),
+            prompt_id: None,
+            model_id: None,
+            language: None,
         },
     );
 
@@ -609,6 +903,11 @@ pub fn get_default_settings() -> AppSettings {
         autostart_enabled: default_autostart_enabled(),
         update_checks_enabled: default_update_checks_enabled(),
         selected_model: "".to_string(),
+        model_catalog_url: None,
+        model_mirror_url: None,
+        model_download_proxy: None,
+        max_concurrent_downloads: default_max_concurrent_downloads(),
+        download_speed_limit: None,
         always_on_microphone: false,
         selected_microphone: None,
         clamshell_microphone: None,
@@ -619,8 +918,17 @@ pub fn get_default_settings() -> AppSettings {
         debug_mode: false,
         log_level: default_log_level(),
         custom_words: Vec::new(),
+        snippets: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
+        replacement_rules: Vec::new(),
+        filler_word_filter_enabled: default_filler_word_filter_enabled(),
+        filler_word_overrides: HashMap::new(),
+        collapse_stutters: default_collapse_stutters(),
+        spoken_punctuation_enabled: false,
+        spoken_formatting_enabled: default_spoken_formatting_enabled(),
+        spoken_punctuation_custom: Vec::new(),
+        spoken_punctuation_disabled: Vec::new(),
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
         paste_method: PasteMethod::default(),
@@ -628,16 +936,24 @@ pub fn get_default_settings() -> AppSettings {
         post_process_enabled: default_post_process_enabled(),
         post_process_provider_id: default_post_process_provider_id(),
         post_process_providers: default_post_process_providers(),
+        post_process_fallback_provider_ids: Vec::new(),
         post_process_api_keys: default_post_process_api_keys(),
         post_process_models: default_post_process_models(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: Some("default_improve_transcriptions".to_string()),
+        post_process_language_prompts: HashMap::new(),
+        app_profiles: Vec::new(),
+        voice_commands_enabled: false,
+        voice_commands: default_voice_commands(),
+        voice_command_threshold: default_voice_command_threshold(),
         mute_while_recording: false,
         append_trailing_space: false,
         app_language: default_app_language(),
         experimental_enabled: false,
         keyboard_implementation: KeyboardImplementation::default(),
         paste_delay_ms: default_paste_delay_ms(),
+        streaming_transcription: false,
+        control_socket_enabled: false,
     }
 }
 
@@ -648,6 +964,118 @@ impl AppSettings {
             .find(|provider| provider.id == self.post_process_provider_id)
     }
 
+    pub fn app_profile_for(&self, app: &ActiveApp) -> Option<&AppProfile> {
+        self.app_profiles
+            .iter()
+            .find(|profile| profile.app_matchers.iter().any(|m| app.matches(m)))
+    }
+
+    /// Apply a profile's overrides to this settings snapshot. Nothing is persisted.
+    pub fn apply_app_profile(&mut self, profile: &AppProfile) {
+        if let Some(prompt_id) = &profile.prompt_id {
+            // A prompt picked for the app wins over the per-language ones
+            self.post_process_selected_prompt_id = Some(prompt_id.clone());
+            self.post_process_language_prompts.clear();
+        }
+        if let Some(paste_method) = profile.paste_method {
+            self.paste_method = paste_method;
+        }
+        if let Some(append_trailing_space) = profile.append_trailing_space {
+            self.append_trailing_space = append_trailing_space;
+        }
+        if let Some(language) = &profile.language {
+            self.selected_language = language.clone();
+        }
+    }
+
+    /// The prompt for post-processing text in `language` (e.g. `de` or `zh-Hant`): the
+    /// one assigned to the language or its base code, otherwise the selected prompt.
+    pub fn post_process_prompt_id(&self, language: &str) -> Option<&String> {
+        let base = language.split(['-', '_']).next().unwrap_or(language);
+        self.post_process_language_prompts
+            .get(language)
+            .or_else(|| self.post_process_language_prompts.get(base))
+            .or(self.post_process_selected_prompt_id.as_ref())
+    }
+
+    /// The filler words removed for `language`: the built-in list with the user's
+    /// additions and removals. Empty when filtering is off. "auto" means detection
+    /// found nothing, so the app's language is used instead.
+    pub fn filler_words(&self, language: &str) -> Vec<String> {
+        if !self.filler_word_filter_enabled {
+            return Vec::new();
+        }
+
+        let language = if language == "auto" {
+            self.app_language.as_str()
+        } else {
+            language
+        };
+        let base = language.split(['-', '_']).next().unwrap_or(language);
+        let overrides = self.filler_word_overrides.get(&base.to_lowercase());
+        let removed: Vec<String> = overrides
+            .map(|o| o.removed.iter().map(|w| w.trim().to_lowercase()).collect())
+            .unwrap_or_default();
+
+        let mut words: Vec<String> = builtin_filler_words(language)
+            .iter()
+            .map(|word| word.to_string())
+            .filter(|word| !removed.contains(word))
+            .collect();
+        if let Some(overrides) = overrides {
+            words.extend(overrides.added.iter().cloned());
+        }
+        words
+    }
+
+    /// The spoken punctuation vocabulary for `language`: the user's tokens followed by
+    /// the enabled built-in ones. Empty when the feature is off.
+    pub fn spoken_punctuation_tokens(&self, language: &str) -> Vec<SpokenToken> {
+        if !self.spoken_punctuation_enabled {
+            return Vec::new();
+        }
+
+        let builtin = builtin_tokens(language, self.spoken_formatting_enabled)
+            .into_iter()
+            .filter(|token| {
+                !self
+                    .spoken_punctuation_disabled
+                    .iter()
+                    .any(|phrase| phrase.trim().eq_ignore_ascii_case(&token.phrase))
+            });
+        self.spoken_punctuation_custom
+            .iter()
+            .cloned()
+            .chain(builtin)
+            .collect()
+    }
+
+    /// Transcribe bindings added by the user on top of the built-in ones.
+    pub fn custom_bindings(&self) -> Vec<ShortcutBinding> {
+        let defaults = get_default_settings().bindings;
+        self.bindings
+            .values()
+            .filter(|binding| !defaults.contains_key(&binding.id))
+            .cloned()
+            .collect()
+    }
+
+    /// The active provider followed by the configured fallbacks, without duplicates.
+    pub fn post_process_provider_chain(&self) -> Vec<&PostProcessProvider> {
+        let mut chain: Vec<&PostProcessProvider> = Vec::new();
+        let ids = std::iter::once(&self.post_process_provider_id)
+            .chain(self.post_process_fallback_provider_ids.iter());
+        for id in ids {
+            if chain.iter().any(|provider| &provider.id == id) {
+                continue;
+            }
+            if let Some(provider) = self.post_process_provider(id) {
+                chain.push(provider);
+            }
+        }
+        chain
+    }
+
     pub fn post_process_provider(&self, provider_id: &str) -> Option<&PostProcessProvider> {
         self.post_process_providers
             .iter()
@@ -7,7 +7,7 @@ use log::warn;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
-use crate::actions::ACTION_MAP;
+use crate::actions::action_for;
 use crate::managers::audio::AudioRecordingManager;
 use crate::settings::get_settings;
 use crate::ManagedToggleState;
@@ -33,7 +33,7 @@ pub fn handle_shortcut_event(
 ) {
     let settings = get_settings(app);
 
-    let Some(action) = ACTION_MAP.get(binding_id) else {
+    let Some(action) = action_for(binding_id) else {
         warn!(
             "No action defined in ACTION_MAP for shortcut ID '{}'. Shortcut: '{}', Pressed: {}",
             binding_id, hotkey_string, is_pressed
@@ -437,6 +437,9 @@ pub fn init_shortcuts(app: &AppHandle) -> Result<(), String> {
         if id == "transcribe_with_post_process" && !user_settings.post_process_enabled {
             continue;
         }
+        if id == "voice_command" && !user_settings.voice_commands_enabled {
+            continue;
+        }
 
         let binding = user_settings
             .bindings
@@ -452,6 +455,15 @@ pub fn init_shortcuts(app: &AppHandle) -> Result<(), String> {
         }
     }
 
+    for binding in user_settings.custom_bindings() {
+        if let Err(e) = state.register(&binding) {
+            error!(
+                "Failed to register handy-keys shortcut {} during init: {}",
+                binding.id, e
+            );
+        }
+    }
+
     app.manage(state);
     info!("handy-keys shortcuts initialized");
     Ok(())
@@ -16,21 +16,30 @@ mod tauri_impl;
 use log::{error, info, warn};
 use serde::Serialize;
 use specta::Type;
+use std::collections::HashMap;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
+use crate::audio_toolkit::{
+    apply_replacement_rules, builtin_filler_words, builtin_tokens, ReplacementRule, SpokenToken,
+};
+use crate::managers::model::ModelManager;
 use crate::settings::{
-    self, get_settings, ClipboardHandling, KeyboardImplementation, LLMPrompt, OverlayPosition,
-    PasteMethod, ShortcutBinding, SoundTheme, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID,
+    self, get_settings, AppProfile, ClipboardHandling, FillerWordOverrides, KeyboardImplementation,
+    LLMPrompt, OverlayPosition, PasteMethod, PromptExample, ProviderProtocol, ShortcutBinding,
+    Snippet, SoundTheme, VoiceCommand, VoiceCommandAction, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID,
     APPLE_INTELLIGENCE_PROVIDER_ID,
 };
-use crate::tray;
+use crate::snippets::SnippetExport;
+use crate::{actions, tray};
 
 // Note: Commands are accessed via shortcut::handy_keys:: in lib.rs
 
 /// Initialize shortcuts using the configured implementation
 pub fn init_shortcuts(app: &AppHandle) {
     let user_settings = settings::load_or_create_app_settings(app);
+    actions::refresh_actions(&user_settings);
 
     // Check which implementation to use
     match user_settings.keyboard_implementation {
@@ -233,6 +242,131 @@ pub fn resume_binding(app: AppHandle, id: String) -> Result<(), String> {
     Ok(())
 }
 
+fn validate_binding_overrides(
+    app: &AppHandle,
+    settings: &settings::AppSettings,
+    prompt_id: Option<&String>,
+    model_id: Option<&String>,
+) -> Result<(), String> {
+    if let Some(prompt_id) = prompt_id {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Prompt with id '{}' not found", prompt_id));
+        }
+    }
+    if let Some(model_id) = model_id {
+        if app
+            .state::<Arc<ModelManager>>()
+            .get_model_info(model_id)
+            .is_none()
+        {
+            return Err(format!("Model with id '{}' not found", model_id));
+        }
+    }
+    Ok(())
+}
+
+/// Add a transcribe shortcut with its own prompt, model and language.
+#[tauri::command]
+#[specta::specta]
+pub fn add_transcribe_binding(
+    app: AppHandle,
+    name: String,
+    binding: String,
+    prompt_id: Option<String>,
+    model_id: Option<String>,
+    language: Option<String>,
+) -> Result<ShortcutBinding, String> {
+    let mut settings = settings::get_settings(&app);
+
+    if binding.trim().is_empty() {
+        return Err("Binding cannot be empty".to_string());
+    }
+    validate_shortcut_for_implementation(&binding, settings.keyboard_implementation)?;
+    validate_binding_overrides(&app, &settings, prompt_id.as_ref(), model_id.as_ref())?;
+
+    let new_binding = ShortcutBinding {
+        id: format!("binding_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        description: "Converts your speech into text with custom settings.".to_string(),
+        default_binding: binding.clone(),
+        current_binding: binding,
+        prompt_id,
+        model_id,
+        language,
+    };
+
+    register_shortcut(&app, new_binding.clone())?;
+
+    settings
+        .bindings
+        .insert(new_binding.id.clone(), new_binding.clone());
+    actions::refresh_actions(&settings);
+    settings::write_settings(&app, settings);
+
+    Ok(new_binding)
+}
+
+/// Change the overrides of a transcribe binding. Built-in bindings accept overrides too,
+/// but keep their name.
+#[tauri::command]
+#[specta::specta]
+pub fn update_transcribe_binding(
+    app: AppHandle,
+    id: String,
+    name: Option<String>,
+    prompt_id: Option<String>,
+    model_id: Option<String>,
+    language: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_binding_overrides(&app, &settings, prompt_id.as_ref(), model_id.as_ref())?;
+
+    if id == "cancel" {
+        return Err("The cancel binding has no transcription settings".to_string());
+    }
+    if name.is_some() && !settings.custom_bindings().iter().any(|b| b.id == id) {
+        return Err("Built-in bindings cannot be renamed".to_string());
+    }
+
+    let Some(binding) = settings.bindings.get_mut(&id) else {
+        return Err(format!("Binding with id '{}' not found", id));
+    };
+    if let Some(name) = name {
+        binding.name = name;
+    }
+    binding.prompt_id = prompt_id;
+    binding.model_id = model_id;
+    binding.language = language;
+
+    actions::refresh_actions(&settings);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Remove a user-defined transcribe binding and its shortcut.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_transcribe_binding(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let Some(binding) = settings.custom_bindings().into_iter().find(|b| b.id == id) else {
+        return Err(format!("Custom binding with id '{}' not found", id));
+    };
+
+    if let Err(e) = unregister_shortcut(&app, binding) {
+        warn!("Failed to unregister shortcut '{}': {}", id, e);
+    }
+
+    settings.bindings.remove(&id);
+    actions::refresh_actions(&settings);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 // ============================================================================
 // Keyboard Implementation Switching
 // ============================================================================
@@ -396,6 +530,9 @@ fn register_all_shortcuts_for_implementation(
         if id == "transcribe_with_post_process" && !current_settings.post_process_enabled {
             continue;
         }
+        if id == "voice_command" && !current_settings.voice_commands_enabled {
+            continue;
+        }
 
         let mut binding = current_settings
             .bindings
@@ -434,6 +571,33 @@ fn register_all_shortcuts_for_implementation(
         }
     }
 
+    // Custom bindings have no built-in default to fall back to, so invalid ones stay
+    // unregistered until the user picks a new shortcut
+    for binding in current_settings.custom_bindings() {
+        if let Err(e) =
+            validate_shortcut_for_implementation(&binding.current_binding, implementation)
+        {
+            warn!(
+                "Custom shortcut '{}' ({}) is invalid for {:?}: {}",
+                binding.id, binding.current_binding, implementation, e
+            );
+            continue;
+        }
+
+        let id = binding.id.clone();
+        let result = match implementation {
+            KeyboardImplementation::Tauri => tauri_impl::register_shortcut(app, binding),
+            KeyboardImplementation::HandyKeys => handy_keys::register_shortcut(app, binding),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to register shortcut '{}' for {:?}: {}",
+                id, implementation, e
+            );
+        }
+    }
+
     // Save settings if any bindings were reset
     if !reset_bindings.is_empty() {
         settings::write_settings(app, current_settings);
@@ -646,6 +810,123 @@ pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), Str
     Ok(())
 }
 
+fn validate_snippet(trigger: &str) -> Result<(), String> {
+    if !trigger.chars().any(char::is_alphanumeric) {
+        return Err("Snippet trigger needs at least one word".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_snippet(app: AppHandle, trigger: String, content: String) -> Result<Snippet, String> {
+    validate_snippet(&trigger)?;
+    let mut settings = settings::get_settings(&app);
+
+    let snippet = Snippet {
+        id: format!("snippet_{}", chrono::Utc::now().timestamp_millis()),
+        trigger: trigger.trim().to_string(),
+        content,
+    };
+
+    settings.snippets.push(snippet.clone());
+    settings::write_settings(&app, settings);
+
+    Ok(snippet)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_snippet(app: AppHandle, snippet: Snippet) -> Result<(), String> {
+    validate_snippet(&snippet.trigger)?;
+    let mut settings = settings::get_settings(&app);
+
+    if let Some(existing) = settings.snippets.iter_mut().find(|s| s.id == snippet.id) {
+        *existing = Snippet {
+            trigger: snippet.trigger.trim().to_string(),
+            ..snippet
+        };
+        settings::write_settings(&app, settings);
+        Ok(())
+    } else {
+        Err(format!("Snippet with id '{}' not found", snippet.id))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_snippet(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let original_len = settings.snippets.len();
+    settings.snippets.retain(|s| s.id != id);
+    if settings.snippets.len() == original_len {
+        return Err(format!("Snippet with id '{}' not found", id));
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Write the snippet library to a JSON file. Returns the number of snippets written.
+#[tauri::command]
+#[specta::specta]
+pub fn export_snippets(app: AppHandle, path: String) -> Result<usize, String> {
+    let settings = settings::get_settings(&app);
+    let exported: Vec<SnippetExport> = settings
+        .snippets
+        .iter()
+        .map(|s| SnippetExport {
+            trigger: s.trigger.clone(),
+            content: s.content.clone(),
+        })
+        .collect();
+
+    let json = serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(exported.len())
+}
+
+/// Read snippets from a JSON file written by [`export_snippets`]. Imported snippets
+/// replace existing ones with the same trigger; with `replace_existing` the whole
+/// library is replaced. Returns the number of snippets imported.
+#[tauri::command]
+#[specta::specta]
+pub fn import_snippets(
+    app: AppHandle,
+    path: String,
+    replace_existing: bool,
+) -> Result<usize, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let imported: Vec<SnippetExport> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid snippet file: {}", e))?;
+    for snippet in &imported {
+        validate_snippet(&snippet.trigger)?;
+    }
+
+    let mut settings = settings::get_settings(&app);
+    if replace_existing {
+        settings.snippets.clear();
+    }
+
+    let base_id = chrono::Utc::now().timestamp_millis();
+    for (i, snippet) in imported.iter().enumerate() {
+        let trigger = snippet.trigger.trim();
+        settings
+            .snippets
+            .retain(|s| !s.trigger.eq_ignore_ascii_case(trigger));
+        settings.snippets.push(Snippet {
+            id: format!("snippet_{}_{}", base_id, i),
+            trigger: trigger.to_string(),
+            content: snippet.content.clone(),
+        });
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(imported.len())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_word_correction_threshold_setting(
@@ -658,6 +939,137 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_spoken_punctuation_setting(
+    app: AppHandle,
+    enabled: bool,
+    formatting: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.spoken_punctuation_enabled = enabled;
+    settings.spoken_formatting_enabled = formatting;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_spoken_punctuation(
+    app: AppHandle,
+    custom: Vec<SpokenToken>,
+    disabled: Vec<String>,
+) -> Result<(), String> {
+    if custom.iter().any(|token| token.phrase.trim().is_empty()) {
+        return Err("Spoken punctuation phrases cannot be empty".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.spoken_punctuation_custom = custom;
+    settings.spoken_punctuation_disabled = disabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_filler_word_filter_setting(
+    app: AppHandle,
+    enabled: bool,
+    collapse_stutters: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.filler_word_filter_enabled = enabled;
+    settings.collapse_stutters = collapse_stutters;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Replace the user's additions to and removals from one language's filler words.
+/// `language` is a base code such as `en` or `it`.
+#[tauri::command]
+#[specta::specta]
+pub fn update_filler_words(
+    app: AppHandle,
+    language: String,
+    added: Vec<String>,
+    removed: Vec<String>,
+) -> Result<(), String> {
+    let language = language.trim().to_lowercase();
+    if language.is_empty() {
+        return Err("Language cannot be empty".to_string());
+    }
+
+    let clean = |words: Vec<String>| -> Vec<String> {
+        words
+            .into_iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect()
+    };
+    let overrides = FillerWordOverrides {
+        added: clean(added),
+        removed: clean(removed),
+    };
+
+    let mut settings = settings::get_settings(&app);
+    if overrides.added.is_empty() && overrides.removed.is_empty() {
+        settings.filler_word_overrides.remove(&language);
+    } else {
+        settings.filler_word_overrides.insert(language, overrides);
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// The built-in filler words for a language, for display in the UI.
+#[tauri::command]
+#[specta::specta]
+pub fn get_builtin_filler_words(language: String) -> Vec<String> {
+    builtin_filler_words(&language)
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_replacement_rules(app: AppHandle, rules: Vec<ReplacementRule>) -> Result<(), String> {
+    validate_replacement_rules(&rules)?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.replacement_rules = rules;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Run replacement rules against sample text without saving them, so the UI can
+/// show a preview while the user edits.
+#[tauri::command]
+#[specta::specta]
+pub fn preview_replacement_rules(
+    rules: Vec<ReplacementRule>,
+    text: String,
+) -> Result<String, String> {
+    validate_replacement_rules(&rules)?;
+    Ok(apply_replacement_rules(&text, &rules))
+}
+
+/// Checks the enabled rules, so a disabled rule can be saved while it's being fixed.
+fn validate_replacement_rules(rules: &[ReplacementRule]) -> Result<(), String> {
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        rule.compile()?;
+    }
+    Ok(())
+}
+
+/// The built-in spoken punctuation vocabulary for a language, for display in the UI.
+#[tauri::command]
+#[specta::specta]
+pub fn get_builtin_spoken_punctuation(language: String) -> Vec<SpokenToken> {
+    builtin_tokens(&language, true)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
@@ -665,6 +1077,7 @@ pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(),
     let parsed = match method.as_str() {
         "ctrl_v" => PasteMethod::CtrlV,
         "direct" => PasteMethod::Direct,
+        "direct_streaming" => PasteMethod::DirectStreaming,
         "none" => PasteMethod::None,
         "shift_insert" => PasteMethod::ShiftInsert,
         "ctrl_shift_v" => PasteMethod::CtrlShiftV,
@@ -721,6 +1134,109 @@ pub fn change_post_process_enabled_setting(app: AppHandle, enabled: bool) -> Res
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_voice_commands_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.voice_commands_enabled = enabled;
+    settings::write_settings(&app, settings.clone());
+
+    // Register or unregister the voice command shortcut
+    if let Some(binding) = settings.bindings.get("voice_command").cloned() {
+        if enabled {
+            let _ = register_shortcut(&app, binding);
+        } else {
+            let _ = unregister_shortcut(&app, binding);
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_voice_command_threshold_setting(
+    app: AppHandle,
+    threshold: f64,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.voice_command_threshold = threshold;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+fn validate_voice_command(phrase: &str, action: &VoiceCommandAction) -> Result<(), String> {
+    if phrase.trim().is_empty() {
+        return Err("Voice command phrase cannot be empty".to_string());
+    }
+    match action {
+        VoiceCommandAction::Keys { keys } => crate::input::parse_key_sequence(keys).map(|_| ()),
+        VoiceCommandAction::Shell { command } if command.trim().is_empty() => {
+            Err("Shell command cannot be empty".to_string())
+        }
+        VoiceCommandAction::Shell { .. } => Ok(()),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_voice_command(
+    app: AppHandle,
+    phrase: String,
+    action: VoiceCommandAction,
+) -> Result<VoiceCommand, String> {
+    validate_voice_command(&phrase, &action)?;
+    let mut settings = settings::get_settings(&app);
+
+    let command = VoiceCommand {
+        id: format!("command_{}", chrono::Utc::now().timestamp_millis()),
+        phrase: phrase.trim().to_string(),
+        action,
+    };
+
+    settings.voice_commands.push(command.clone());
+    settings::write_settings(&app, settings);
+
+    Ok(command)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_voice_command(app: AppHandle, command: VoiceCommand) -> Result<(), String> {
+    validate_voice_command(&command.phrase, &command.action)?;
+    let mut settings = settings::get_settings(&app);
+
+    if let Some(existing) = settings
+        .voice_commands
+        .iter_mut()
+        .find(|c| c.id == command.id)
+    {
+        *existing = VoiceCommand {
+            phrase: command.phrase.trim().to_string(),
+            ..command
+        };
+        settings::write_settings(&app, settings);
+        Ok(())
+    } else {
+        Err(format!("Voice command with id '{}' not found", command.id))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_voice_command(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let original_len = settings.voice_commands.len();
+    settings.voice_commands.retain(|c| c.id != id);
+    if settings.voice_commands.len() == original_len {
+        return Err(format!("Voice command with id '{}' not found", id));
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_experimental_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -759,6 +1275,70 @@ pub fn change_post_process_base_url_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_protocol_setting(
+    app: AppHandle,
+    provider_id: String,
+    protocol: ProviderProtocol,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let provider = settings
+        .post_process_provider_mut(&provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+
+    // Built-in providers have a fixed protocol
+    if !provider.allow_base_url_edit {
+        return Err(format!(
+            "Provider '{}' does not allow changing the protocol",
+            provider.label
+        ));
+    }
+
+    provider.protocol = protocol;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_request_setting(
+    app: AppHandle,
+    provider_id: String,
+    timeout_secs: Option<u64>,
+    max_retries: u32,
+    retry_on_timeout: bool,
+) -> Result<(), String> {
+    if timeout_secs == Some(0) {
+        return Err("Timeout must be at least one second".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    let provider = settings
+        .post_process_provider_mut(&provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+    provider.timeout_secs = timeout_secs;
+    provider.max_retries = max_retries;
+    provider.retry_on_timeout = retry_on_timeout;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_post_process_fallback_providers(
+    app: AppHandle,
+    provider_ids: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    for provider_id in &provider_ids {
+        validate_provider_exists(&settings, provider_id)?;
+    }
+    settings.post_process_fallback_provider_ids = provider_ids;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 /// Generic helper to validate provider exists
 fn validate_provider_exists(
     settings: &settings::AppSettings,
@@ -818,6 +1398,9 @@ pub fn add_post_process_prompt(
     app: AppHandle,
     name: String,
     prompt: String,
+    system_prompt: Option<String>,
+    examples: Option<Vec<PromptExample>>,
+    variables: Option<HashMap<String, String>>,
 ) -> Result<LLMPrompt, String> {
     let mut settings = settings::get_settings(&app);
 
@@ -828,6 +1411,9 @@ pub fn add_post_process_prompt(
         id: id.clone(),
         name,
         prompt,
+        system_prompt: system_prompt.filter(|s| !s.is_empty()),
+        examples: examples.unwrap_or_default(),
+        variables: variables.unwrap_or_default(),
     };
 
     settings.post_process_prompts.push(new_prompt.clone());
@@ -843,6 +1429,9 @@ pub fn update_post_process_prompt(
     id: String,
     name: String,
     prompt: String,
+    system_prompt: Option<String>,
+    examples: Option<Vec<PromptExample>>,
+    variables: Option<HashMap<String, String>>,
 ) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
 
@@ -853,6 +1442,16 @@ pub fn update_post_process_prompt(
     {
         existing_prompt.name = name;
         existing_prompt.prompt = prompt;
+        // Fields left out by the caller keep their current values
+        if let Some(system_prompt) = system_prompt {
+            existing_prompt.system_prompt = Some(system_prompt).filter(|s| !s.is_empty());
+        }
+        if let Some(examples) = examples {
+            existing_prompt.examples = examples;
+        }
+        if let Some(variables) = variables {
+            existing_prompt.variables = variables;
+        }
         settings::write_settings(&app, settings);
         Ok(())
     } else {
@@ -884,6 +1483,92 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
             settings.post_process_prompts.first().map(|p| p.id.clone());
     }
 
+    // App profiles and bindings using the deleted prompt fall back to the selected one
+    for profile in &mut settings.app_profiles {
+        if profile.prompt_id.as_ref() == Some(&id) {
+            profile.prompt_id = None;
+        }
+    }
+    for binding in settings.bindings.values_mut() {
+        if binding.prompt_id.as_ref() == Some(&id) {
+            binding.prompt_id = None;
+        }
+    }
+    settings
+        .post_process_language_prompts
+        .retain(|_, prompt_id| prompt_id != &id);
+    actions::refresh_actions(&settings);
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+fn validate_app_profile(
+    settings: &settings::AppSettings,
+    profile: &AppProfile,
+) -> Result<(), String> {
+    if profile.app_matchers.iter().all(|m| m.trim().is_empty()) {
+        return Err("An app profile needs at least one application to match".to_string());
+    }
+    if let Some(prompt_id) = &profile.prompt_id {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Prompt with id '{}' not found", prompt_id));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_app_profile(app: AppHandle, profile: AppProfile) -> Result<AppProfile, String> {
+    let mut settings = settings::get_settings(&app);
+    validate_app_profile(&settings, &profile)?;
+
+    let new_profile = AppProfile {
+        id: format!("profile_{}", chrono::Utc::now().timestamp_millis()),
+        ..profile
+    };
+
+    settings.app_profiles.push(new_profile.clone());
+    settings::write_settings(&app, settings);
+
+    Ok(new_profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_app_profile(app: AppHandle, profile: AppProfile) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_app_profile(&settings, &profile)?;
+
+    if let Some(existing) = settings
+        .app_profiles
+        .iter_mut()
+        .find(|p| p.id == profile.id)
+    {
+        *existing = profile;
+        settings::write_settings(&app, settings);
+        Ok(())
+    } else {
+        Err(format!("App profile with id '{}' not found", profile.id))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_app_profile(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let original_len = settings.app_profiles.len();
+    settings.app_profiles.retain(|p| p.id != id);
+    if settings.app_profiles.len() == original_len {
+        return Err(format!("App profile with id '{}' not found", id));
+    }
+
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -948,6 +1633,42 @@ pub fn set_post_process_selected_prompt(app: AppHandle, id: String) -> Result<()
     Ok(())
 }
 
+/// Use `prompt_id` for transcriptions in `language` (e.g. `de` or `zh-Hant`), or go back
+/// to the selected prompt with `None`.
+#[tauri::command]
+#[specta::specta]
+pub fn set_post_process_language_prompt(
+    app: AppHandle,
+    language: String,
+    prompt_id: Option<String>,
+) -> Result<(), String> {
+    let language = language.trim().to_string();
+    if language.is_empty() || language == "auto" {
+        return Err("A specific language is required".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    match prompt_id {
+        Some(prompt_id) => {
+            if !settings
+                .post_process_prompts
+                .iter()
+                .any(|p| p.id == prompt_id)
+            {
+                return Err(format!("Prompt with id '{}' not found", prompt_id));
+            }
+            settings
+                .post_process_language_prompts
+                .insert(language, prompt_id);
+        }
+        None => {
+            settings.post_process_language_prompts.remove(&language);
+        }
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -966,6 +1687,31 @@ pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Re
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_streaming_transcription_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.streaming_transcription = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_control_socket_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.control_socket_enabled = enabled;
+    settings::write_settings(&app, settings);
+
+    if enabled {
+        crate::control_socket::start_server(&app);
+    } else {
+        crate::control_socket::stop_server(&app);
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_app_language_setting(app: AppHandle, language: String) -> Result<(), String> {
@@ -25,6 +25,9 @@ pub fn init_shortcuts(app: &AppHandle) {
         if id == "transcribe_with_post_process" && !user_settings.post_process_enabled {
             continue;
         }
+        if id == "voice_command" && !user_settings.voice_commands_enabled {
+            continue;
+        }
         let binding = user_settings
             .bindings
             .get(&id)
@@ -35,6 +38,13 @@ pub fn init_shortcuts(app: &AppHandle) {
             error!("Failed to register shortcut {} during init: {}", id, e);
         }
     }
+
+    for binding in user_settings.custom_bindings() {
+        let id = binding.id.clone();
+        if let Err(e) = register_shortcut(app, binding) {
+            error!("Failed to register shortcut {} during init: {}", id, e);
+        }
+    }
 }
 
 /// Validate a shortcut string for the Tauri global-shortcut implementation.
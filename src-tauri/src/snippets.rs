@@ -0,0 +1,135 @@
+//! Snippets: spoken triggers such as "insert my signature" that expand to stored,
+//! possibly multi-line, text before it is pasted.
+
+use crate::settings::Snippet;
+use log::warn;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// A snippet as stored in export files, without its settings id
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SnippetExport {
+    pub trigger: String,
+    pub content: String,
+}
+
+/// Trigger words lowercased and stripped of the punctuation a model may add
+fn trigger_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// The snippet whose trigger is the entire utterance, if any.
+pub fn whole_utterance<'a>(snippets: &'a [Snippet], text: &str) -> Option<&'a Snippet> {
+    let words = trigger_words(text);
+    if words.is_empty() {
+        return None;
+    }
+    snippets
+        .iter()
+        .find(|snippet| trigger_words(&snippet.trigger) == words)
+}
+
+fn trigger_regex(trigger: &str) -> Option<Regex> {
+    let words = trigger_words(trigger);
+    if words.is_empty() {
+        return None;
+    }
+    let words: Vec<String> = words.iter().map(|w| regex::escape(w)).collect();
+    // Allow the punctuation a model puts between and right after the spoken words
+    let pattern = format!(r"\b{}\b[.!?,]?", words.join(r"[\s,.;:!?]+"));
+    match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+        Ok(regex) => Some(regex),
+        Err(e) => {
+            warn!("Invalid snippet trigger '{}': {}", trigger, e);
+            None
+        }
+    }
+}
+
+/// Whether any snippet trigger appears in `text`.
+pub fn contains_trigger(text: &str, snippets: &[Snippet]) -> bool {
+    snippets
+        .iter()
+        .any(|snippet| trigger_regex(&snippet.trigger).is_some_and(|regex| regex.is_match(text)))
+}
+
+/// Replace every spoken trigger in `text` with its snippet. Longer triggers are
+/// expanded first so "insert signature short" wins over "insert signature".
+pub fn expand_snippets(text: &str, snippets: &[Snippet]) -> String {
+    let mut ordered: Vec<&Snippet> = snippets.iter().collect();
+    ordered.sort_by_key(|snippet| std::cmp::Reverse(trigger_words(&snippet.trigger).len()));
+
+    let mut result = text.to_string();
+    for snippet in ordered {
+        if let Some(regex) = trigger_regex(&snippet.trigger) {
+            result = regex
+                .replace_all(&result, NoExpand(&snippet.content))
+                .into_owned();
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(trigger: &str, content: &str) -> Snippet {
+        Snippet {
+            id: trigger.to_string(),
+            trigger: trigger.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn matches_whole_utterance_ignoring_case_and_punctuation() {
+        let snippets = vec![snippet("insert my signature", "Best,\nAlex")];
+        assert_eq!(
+            whole_utterance(&snippets, "Insert my signature.").map(|s| s.id.as_str()),
+            Some("insert my signature")
+        );
+        assert!(whole_utterance(&snippets, "please insert my signature").is_none());
+        assert!(whole_utterance(&snippets, "").is_none());
+    }
+
+    #[test]
+    fn expands_inline_triggers() {
+        let snippets = vec![
+            snippet("insert my signature", "Best,\nAlex"),
+            snippet("insert standup template", "Yesterday:\nToday:\n$blockers"),
+        ];
+        assert_eq!(
+            expand_snippets("Thanks for the update. Insert my signature.", &snippets),
+            "Thanks for the update. Best,\nAlex"
+        );
+        assert_eq!(
+            expand_snippets("insert, standup template", &snippets),
+            "Yesterday:\nToday:\n$blockers"
+        );
+        assert_eq!(
+            expand_snippets("insert my signatures", &snippets),
+            "insert my signatures"
+        );
+        assert!(contains_trigger("Thanks. Insert my signature.", &snippets));
+        assert!(!contains_trigger("insert my signatures", &snippets));
+    }
+
+    #[test]
+    fn prefers_longer_triggers() {
+        let snippets = vec![
+            snippet("insert signature", "long"),
+            snippet("insert signature short", "short"),
+        ];
+        assert_eq!(
+            expand_snippets("insert signature short", &snippets),
+            "short"
+        );
+    }
+}
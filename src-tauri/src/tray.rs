@@ -213,6 +213,10 @@ mod tests {
             transcription_text: transcription.to_string(),
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
+            post_process_provider: None,
+            segments: Vec::new(),
+            words: None,
+            language: None,
         }
     }
 
@@ -33,12 +33,16 @@ pub fn cancel_current_operation(app: &AppHandle) {
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
 
+    // Stop typing streamed post-processing output and abort its request
+    cancel_streaming_paste();
+
     // Update tray icon and hide overlay
     change_tray_icon(app, crate::tray::TrayIconState::Idle);
     hide_recording_overlay(app);
 
-    // Unload model if immediate unload is enabled
+    // Drop any streaming transcription and unload model if immediate unload is enabled
     let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.cancel_streaming();
     tm.maybe_unload_immediately("cancellation");
 
     info!("Operation cancellation completed - returned to idle state");
@@ -67,3 +71,8 @@ pub fn is_kde_plasma() -> bool {
 pub fn is_kde_wayland() -> bool {
     is_wayland() && is_kde_plasma()
 }
+
+/// Serde default for boolean fields that are on unless set otherwise.
+pub fn default_true() -> bool {
+    true
+}
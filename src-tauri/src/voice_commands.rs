@@ -0,0 +1,68 @@
+//! Voice commands: spoken phrases that press keys or run a shell command instead of
+//! pasting the transcription. Used by the `voice_command` binding.
+
+use crate::audio_toolkit::{match_phrase, phrase_matches_exactly};
+use crate::input::{self, EnigoState};
+use crate::settings::{AppSettings, VoiceCommand, VoiceCommandAction};
+use log::{debug, error, info};
+use std::process::Command;
+use tauri::{AppHandle, Manager};
+
+/// Find the command whose phrase best matches the whole utterance. Shell commands
+/// only run when the phrase was said exactly, since a near miss could run the wrong
+/// command; key commands also match approximately.
+pub fn find_command<'a>(settings: &'a AppSettings, text: &str) -> Option<&'a VoiceCommand> {
+    if let Some(command) = settings
+        .voice_commands
+        .iter()
+        .find(|command| phrase_matches_exactly(text, &command.phrase))
+    {
+        return Some(command);
+    }
+
+    let key_commands: Vec<&VoiceCommand> = settings
+        .voice_commands
+        .iter()
+        .filter(|command| matches!(command.action, VoiceCommandAction::Keys { .. }))
+        .collect();
+    let phrases: Vec<String> = key_commands
+        .iter()
+        .map(|command| command.phrase.clone())
+        .collect();
+    let index = match_phrase(text, &phrases, settings.voice_command_threshold)?;
+    key_commands.get(index).copied()
+}
+
+/// Run a command's action. Key sequences must be sent from the main thread.
+pub fn execute(app: &AppHandle, command: &VoiceCommand) -> Result<(), String> {
+    info!("Running voice command '{}'", command.phrase);
+    match &command.action {
+        VoiceCommandAction::Keys { keys } => {
+            let enigo_state = app
+                .try_state::<EnigoState>()
+                .ok_or("Enigo state not initialized")?;
+            let mut enigo = enigo_state
+                .0
+                .lock()
+                .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+            input::send_key_sequence(&mut enigo, keys)
+        }
+        VoiceCommandAction::Shell { command } => spawn_shell(command),
+    }
+}
+
+fn spawn_shell(command: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let spawned = Command::new("cmd").args(["/C", command]).spawn();
+    #[cfg(not(target_os = "windows"))]
+    let spawned = Command::new("sh").args(["-c", command]).spawn();
+
+    let mut child = spawned.map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    let command = command.to_string();
+    // Reap the process in the background so long-running commands don't block dictation
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) => debug!("Voice command '{}' exited with {}", command, status),
+        Err(e) => error!("Failed to wait for voice command '{}': {}", command, e),
+    });
+    Ok(())
+}
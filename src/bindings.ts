@@ -109,6 +109,74 @@ async changeWordCorrectionThresholdSetting(threshold: number) : Promise<Result<n
     else return { status: "error", error: e  as any };
 }
 },
+async changeSpokenPunctuationSetting(enabled: boolean, formatting: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_spoken_punctuation_setting", { enabled, formatting }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSpokenPunctuation(custom: SpokenToken[], disabled: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_spoken_punctuation", { custom, disabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The built-in spoken punctuation vocabulary for a language, for display in the UI.
+ */
+async getBuiltinSpokenPunctuation(language: string) : Promise<SpokenToken[]> {
+    return await TAURI_INVOKE("get_builtin_spoken_punctuation", { language });
+},
+async changeFillerWordFilterSetting(enabled: boolean, collapseStutters: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_filler_word_filter_setting", { enabled, collapseStutters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace the user's additions to and removals from one language's filler words.
+ * `language` is a base code such as `en` or `it`.
+ */
+async updateFillerWords(language: string, added: string[], removed: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_filler_words", { language, added, removed }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The built-in filler words for a language, for display in the UI.
+ */
+async getBuiltinFillerWords(language: string) : Promise<string[]> {
+    return await TAURI_INVOKE("get_builtin_filler_words", { language });
+},
+async updateReplacementRules(rules: ReplacementRule[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_replacement_rules", { rules }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run replacement rules against sample text without saving them, so the UI can
+ * show a preview while the user edits.
+ */
+async previewReplacementRules(rules: ReplacementRule[], text: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_replacement_rules", { rules, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_paste_method_setting", { method }) };
@@ -133,6 +201,46 @@ async changePostProcessEnabledSetting(enabled: boolean) : Promise<Result<null, s
     else return { status: "error", error: e  as any };
 }
 },
+async changeVoiceCommandsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_voice_commands_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeVoiceCommandThresholdSetting(threshold: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_voice_command_threshold_setting", { threshold }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addVoiceCommand(phrase: string, action: VoiceCommandAction) : Promise<Result<VoiceCommand, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_voice_command", { phrase, action }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateVoiceCommand(command: VoiceCommand) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_voice_command", { command }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteVoiceCommand(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_voice_command", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeExperimentalEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_experimental_enabled_setting", { enabled }) };
@@ -149,6 +257,30 @@ async changePostProcessBaseUrlSetting(providerId: string, baseUrl: string) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+async changePostProcessProtocolSetting(providerId: string, protocol: ProviderProtocol) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_protocol_setting", { providerId, protocol }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changePostProcessRequestSetting(providerId: string, timeoutSecs: number | null, maxRetries: number, retryOnTimeout: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_request_setting", { providerId, timeoutSecs, maxRetries, retryOnTimeout }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setPostProcessFallbackProviders(providerIds: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_post_process_fallback_providers", { providerIds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePostProcessApiKeySetting(providerId: string, apiKey: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_post_process_api_key_setting", { providerId, apiKey }) };
@@ -181,17 +313,17 @@ async fetchPostProcessModels(providerId: string) : Promise<Result<string[], stri
     else return { status: "error", error: e  as any };
 }
 },
-async addPostProcessPrompt(name: string, prompt: string) : Promise<Result<LLMPrompt, string>> {
+async addPostProcessPrompt(name: string, prompt: string, systemPrompt: string | null, examples: PromptExample[] | null, variables: Partial<{ [key in string]: string }> | null) : Promise<Result<LLMPrompt, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_post_process_prompt", { name, prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_post_process_prompt", { name, prompt, systemPrompt, examples, variables }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updatePostProcessPrompt(id: string, name: string, prompt: string) : Promise<Result<null, string>> {
+async updatePostProcessPrompt(id: string, name: string, prompt: string, systemPrompt: string | null, examples: PromptExample[] | null, variables: Partial<{ [key in string]: string }> | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt", { id, name, prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt", { id, name, prompt, systemPrompt, examples, variables }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -205,6 +337,30 @@ async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async addAppProfile(profile: AppProfile) : Promise<Result<AppProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_app_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateAppProfile(profile: AppProfile) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_app_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteAppProfile(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_app_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async setPostProcessSelectedPrompt(id: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("set_post_process_selected_prompt", { id }) };
@@ -213,6 +369,18 @@ async setPostProcessSelectedPrompt(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Use `prompt_id` for transcriptions in `language` (e.g. `de` or `zh-Hant`), or go back
+ * to the selected prompt with `None`.
+ */
+async setPostProcessLanguagePrompt(language: string, promptId: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_post_process_language_prompt", { language, promptId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateCustomWords(words: string[]) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_custom_words", { words }) };
@@ -221,6 +389,54 @@ async updateCustomWords(words: string[]) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async addSnippet(trigger: string, content: string) : Promise<Result<Snippet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_snippet", { trigger, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSnippet(snippet: Snippet) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_snippet", { snippet }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSnippet(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_snippet", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write the snippet library to a JSON file. Returns the number of snippets written.
+ */
+async exportSnippets(path: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_snippets", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Read snippets from a JSON file written by [`export_snippets`]. Imported snippets
+ * replace existing ones with the same trigger; with `replace_existing` the whole
+ * library is replaced. Returns the number of snippets imported.
+ */
+async importSnippets(path: string, replaceExisting: boolean) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_snippets", { path, replaceExisting }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Temporarily unregister a binding while the user is editing it in the UI.
  * This avoids firing the action while keys are being recorded.
@@ -233,6 +449,40 @@ async suspendBinding(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Add a transcribe shortcut with its own prompt, model and language.
+ */
+async addTranscribeBinding(name: string, binding: string, promptId: string | null, modelId: string | null, language: string | null) : Promise<Result<ShortcutBinding, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_transcribe_binding", { name, binding, promptId, modelId, language }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Change the overrides of a transcribe binding. Built-in bindings accept overrides too,
+ * but keep their name.
+ */
+async updateTranscribeBinding(id: string, name: string | null, promptId: string | null, modelId: string | null, language: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_transcribe_binding", { id, name, promptId, modelId, language }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Remove a user-defined transcribe binding and its shortcut.
+ */
+async deleteTranscribeBinding(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_transcribe_binding", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Re-register the binding after the user has finished editing.
  */
@@ -260,6 +510,22 @@ async changeAppendTrailingSpaceSetting(enabled: boolean) : Promise<Result<null,
     else return { status: "error", error: e  as any };
 }
 },
+async changeStreamingTranscriptionSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_streaming_transcription_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeControlSocketSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_control_socket_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeAppLanguageSetting(language: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_app_language_setting", { language }) };
@@ -489,6 +755,10 @@ async getModelInfo(modelId: string) : Promise<Result<ModelInfo | null, string>>
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Queue a model download and wait for it to end. Progress is reported as events;
+ * a paused or cancelled download returns early without an error.
+ */
 async downloadModel(modelId: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("download_model", { modelId }) };
@@ -513,6 +783,57 @@ async cancelDownload(modelId: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async pauseDownload(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pause_download", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeDownload(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_download", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async prioritizeDownload(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prioritize_download", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDownloadQueue() : Promise<Result<DownloadQueueState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_download_queue") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setMaxConcurrentDownloads(count: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_max_concurrent_downloads", { count }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Limit the combined speed of model downloads. `None` or 0 removes the limit.
+ */
+async setDownloadSpeedLimit(bytesPerSecond: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_download_speed_limit", { bytesPerSecond }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async setActiveModel(modelId: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("set_active_model", { modelId }) };
@@ -561,6 +882,77 @@ async hasAnyModelsOrDownloads() : Promise<Result<boolean, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Re-read the model manifests and fetch the remote catalog, if one is configured.
+ */
+async refreshModelCatalog() : Promise<Result<ModelInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_model_catalog") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Import a local model file or directory. The engine is inferred from the file layout
+ * unless given, and the model must load with it before it is added.
+ */
+async importModel(request: ModelImportRequest) : Promise<Result<ModelInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_model", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check a downloaded model against its SHA-256 checksum. A corrupted model is
+ * downloaded again, reloading it if it was the active one.
+ */
+async verifyModel(modelId: string) : Promise<Result<ModelVerification, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setModelCatalogUrl(url: string | null) : Promise<Result<ModelInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_model_catalog_url", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setModelMirrorUrl(url: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_model_mirror_url", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setModelDownloadProxy(proxy: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_model_download_proxy", { proxy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Install a model from a `.bin` or `.tar.gz` that was downloaded elsewhere. The model
+ * is matched by file name unless `model_id` is given.
+ */
+async installModelBundle(path: string, modelId: string | null) : Promise<Result<ModelInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_model_bundle", { path, modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateMicrophoneMode(alwaysOn: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_microphone_mode", { alwaysOn }) };
@@ -677,6 +1069,22 @@ async getHistoryEntries() : Promise<Result<HistoryEntry[], string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async getHistoryPage(query: HistoryPageQuery) : Promise<Result<HistoryPage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_page", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchHistory(query: string, filters: HistorySearchFilters | null) : Promise<Result<HistorySearchResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_history", { query, filters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async toggleHistoryEntrySaved(id: number) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("toggle_history_entry_saved", { id }) };
@@ -701,6 +1109,34 @@ async deleteHistoryEntry(id: number) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async exportHistory(request: HistoryExportRequest) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_history", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getHistoryRevisions(historyId: number) : Promise<Result<HistoryRevision[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_revisions", { historyId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-run a history entry through a different model and/or post-processing prompt.
+ * The result is stored as a revision of the entry; the original is never overwritten.
+ */
+async retranscribeHistoryEntry(id: number, modelId: string | null, promptId: string | null) : Promise<Result<HistoryRevision, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("retranscribe_history_entry", { id, modelId, promptId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
@@ -741,15 +1177,216 @@ async isLaptop() : Promise<Result<boolean, string>> {
 
 /** user-defined types **/
 
-export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; paste_delay_ms?: number }
+/**
+ * Overrides applied when dictating into a matching application.
+ */
+export type AppProfile = { id: string; name: string; 
+/**
+ * Process names, window classes or bundle ids this profile applies to
+ */
+app_matchers: string[]; 
+/**
+ * Prompt used when the binding post-processes
+ */
+prompt_id?: string | null; paste_method?: PasteMethod | null; append_trailing_space?: boolean | null; language?: string | null }
+export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; 
+/**
+ * Model catalog merged over the bundled one, fetched at startup
+ */
+model_catalog_url?: string | null; 
+/**
+ * Base URL that model downloads are fetched from instead of their catalog host
+ */
+model_mirror_url?: string | null; 
+/**
+ * HTTP(S) proxy for model and catalog downloads, overriding the system proxy
+ */
+model_download_proxy?: string | null; 
+/**
+ * How many queued model downloads run at the same time
+ */
+max_concurrent_downloads?: number; 
+/**
+ * Combined speed limit for model downloads, in bytes per second
+ */
+download_speed_limit?: number | null; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; snippets?: Snippet[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; 
+/**
+ * Find/replace rules applied in order after custom words
+ */
+replacement_rules?: ReplacementRule[]; filler_word_filter_enabled?: boolean; 
+/**
+ * Keyed by base language code, e.g. `en` or `it`
+ */
+filler_word_overrides?: Partial<{ [key in string]: FillerWordOverrides }>; 
+/**
+ * Collapse stutters like "I I I" into a single word
+ */
+collapse_stutters?: boolean; 
+/**
+ * Write spoken tokens like "comma" or "open paren" as symbols
+ */
+spoken_punctuation_enabled?: boolean; 
+/**
+ * Also handle layout tokens like "new line" and "bullet point"
+ */
+spoken_formatting_enabled?: boolean; 
+/**
+ * User tokens, matched before the built-in vocabulary of the language
+ */
+spoken_punctuation_custom?: SpokenToken[]; 
+/**
+ * Built-in phrases the user switched off
+ */
+spoken_punctuation_disabled?: string[]; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; 
+/**
+ * Providers tried in order when the active one fails
+ */
+post_process_fallback_provider_ids?: string[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; 
+/**
+ * Prompt ids keyed by language code, used instead of the selected prompt when the
+ * transcription is in that language
+ */
+post_process_language_prompts?: Partial<{ [key in string]: string }>; 
+/**
+ * Checked in order, the first profile matching the frontmost app wins
+ */
+app_profiles?: AppProfile[]; voice_commands_enabled?: boolean; voice_commands?: VoiceCommand[]; voice_command_threshold?: number; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; paste_delay_ms?: number; streaming_transcription?: boolean; control_socket_enabled?: boolean }
+export type ArchiveFormat = "zip" | "tar_gz"
 export type AudioDevice = { index: string; name: string; is_default: boolean }
 export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null }
 export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
 export type CustomSounds = { start: boolean; stop: boolean }
+/**
+ * The language a transcription appears to be in, guessed from its text in
+ * "auto" mode. This is not the language the speech model detected.
+ */
+export type DetectedLanguage = { 
+/**
+ * Code in the same form as `selected_language`, e.g. `en`, `de` or `zh-Hant`
+ */
+code: string }
+/**
+ * Snapshot of the download queue, emitted as `model-download-queue`
+ */
+export type DownloadQueueState = { 
+/**
+ * Downloads currently transferring
+ */
+active: string[]; 
+/**
+ * Downloads waiting for a free slot, next first
+ */
+queued: string[]; 
+/**
+ * Downloads stopped by the user, kept as `.partial` files to resume from
+ */
+paused: string[] }
 export type EngineType = "Whisper" | "Parakeet" | "Moonshine"
+export type ExportFormat = "srt" | "vtt" | "jsonl" | "markdown"
+/**
+ * User changes to the built-in filler words of one language
+ */
+export type FillerWordOverrides = { added?: string[]; 
+/**
+ * Built-in filler words that should be kept in the text
+ */
+removed?: string[] }
 export type FoundryConfig = { endpoint_url: string; model_id: string }
 export type FoundryStatus = { installed: boolean; running: boolean; endpoint_url: string | null; model_id: string | null; model_cached: boolean }
-export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null }
+/**
+ * Position in the history list, newest first. Pass the `next_cursor` of one
+ * page to fetch the following page.
+ */
+export type HistoryCursor = { timestamp: number; id: number }
+export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; 
+/**
+ * Provider that produced `post_processed_text`
+ */
+post_process_provider: string | null; 
+/**
+ * Segment timestamps (seconds into the saved WAV file)
+ */
+segments: TimedText[]; 
+/**
+ * Word timestamps, when the engine provides them
+ */
+words: TimedText[] | null; 
+/**
+ * Language guessed from the text when transcribing in "auto" mode
+ */
+language: DetectedLanguage | null }
+/**
+ * Which entries to export and where to write them.
+ * 
+ * Entries are selected by `ids` when given, otherwise by the optional
+ * `[from_timestamp, to_timestamp]` range (unix seconds, inclusive).
+ */
+export type HistoryExportRequest = { format: ExportFormat; ids: number[] | null; from_timestamp: number | null; to_timestamp: number | null; 
+/**
+ * Output file. With `archive` set this is the archive path.
+ */
+destination: string; 
+/**
+ * Bundle the export together with the referenced WAV files.
+ */
+archive: ArchiveFormat | null; 
+/**
+ * Export the post-processed text of entries that have one instead of the
+ * original transcription. Subtitle cues for those entries then span the whole
+ * recording, as the post-processed text has no timestamps of its own.
+ */
+post_processed?: boolean }
+export type HistoryPage = { entries: HistoryEntry[]; 
+/**
+ * Cursor for the next page, `None` when this is the last page
+ */
+next_cursor: HistoryCursor | null }
+export type HistoryPageQuery = { 
+/**
+ * Start after this entry; `None` starts from the newest entry
+ */
+cursor: HistoryCursor | null; limit: number | null; saved_only?: boolean; post_processed_only?: boolean; 
+/**
+ * Only entries at or after this unix timestamp (seconds)
+ */
+from_timestamp: number | null; 
+/**
+ * Only entries at or before this unix timestamp (seconds)
+ */
+to_timestamp: number | null }
+/**
+ * A later transcription of a history entry's audio, e.g. with another model or prompt.
+ */
+export type HistoryRevision = { id: number; history_id: number; created_at: number; 
+/**
+ * Model used for re-transcription, `None` if only post-processing was re-run
+ */
+model_id: string | null; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; post_process_provider: string | null; segments: TimedText[]; words: TimedText[] | null; language: DetectedLanguage | null }
+/**
+ * Optional filters applied on top of a history search.
+ */
+export type HistorySearchFilters = { 
+/**
+ * Only entries at or after this unix timestamp (seconds)
+ */
+from_timestamp: number | null; 
+/**
+ * Only entries at or before this unix timestamp (seconds)
+ */
+to_timestamp: number | null; saved_only?: boolean; 
+/**
+ * Only entries post-processed with exactly this prompt
+ */
+post_process_prompt: string | null; limit: number | null }
+export type HistorySearchResult = { entry: HistoryEntry; 
+/**
+ * Matching text, HTML-escaped, with hits wrapped in `<mark>` tags
+ */
+snippet: string; 
+/**
+ * BM25 relevance, lower is better
+ */
+rank: number }
 /**
  * Result of changing keyboard implementation
  */
@@ -758,18 +1395,199 @@ export type ImplementationChangeResult = { success: boolean;
  * List of binding IDs that were reset to defaults due to incompatibility
  */
 reset_bindings: string[] }
+/**
+ * How an imported model is placed in the models directory
+ */
+export type ImportMode = "copy" | 
+/**
+ * Link to the original so large models aren't stored twice
+ */
+"symlink"
 export type KeyboardImplementation = "tauri" | "handy_keys"
-export type LLMPrompt = { id: string; name: string; prompt: string }
+export type LLMPrompt = { id: string; name: string; 
+/**
+ * User message template, `${output}` is replaced with the transcription
+ */
+prompt: string; 
+/**
+ * Sent as a separate system message when set
+ */
+system_prompt?: string | null; 
+/**
+ * Few-shot examples, sent as user/assistant turns before the transcription
+ */
+examples?: PromptExample[]; 
+/**
+ * Extra `${name}` variables substituted in the system prompt and template
+ */
+variables?: Partial<{ [key in string]: string }> }
 export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
-export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[] }
+/**
+ * A local model file or directory to add to the catalog
+ */
+export type ModelImportRequest = { path: string; 
+/**
+ * Defaults to the file or directory name
+ */
+name?: string | null; 
+/**
+ * Inferred from the file layout when not given
+ */
+engine_type?: EngineType | null; mode?: ImportMode; 
+/**
+ * Defaults to every Whisper language for Whisper models and English otherwise
+ */
+supported_languages?: string[] }
+export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; sha256: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[] }
 export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
 export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
+/**
+ * Result of checking an installed model
+ */
+export type ModelVerification = 
+/**
+ * The files match their checksum
+ */
+"verified" | 
+/**
+ * No checksum is known for the model, so it could not be checked
+ */
+"unverifiable" | 
+/**
+ * The files were corrupted and the model was downloaded again
+ */
+"redownloaded"
 export type OverlayPosition = "none" | "top" | "bottom"
-export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v"
-export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null }
+export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v" | 
+/**
+ * Like `Direct`, but post-processed text is typed as the LLM streams it
+ */
+"direct_streaming"
+export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null; protocol?: ProviderProtocol; 
+/**
+ * Per-attempt request timeout; streamed responses may take longer as long as data keeps arriving.
+ * `None` waits as long as the provider takes.
+ */
+timeout_secs?: number | null; 
+/**
+ * Extra attempts after a connection error, 429 or 5xx response
+ */
+max_retries?: number; 
+/**
+ * Also retry requests that hit `timeout_secs`. Off by default, since a slow
+ * provider usually stays slow and every attempt waits for the full timeout.
+ */
+retry_on_timeout?: boolean }
+export type PromptExample = { input: string; output: string }
+/**
+ * Request/response format spoken by a post-processing provider
+ */
+export type ProviderProtocol = 
+/**
+ * OpenAI-compatible `/chat/completions`
+ */
+"openai_chat" | 
+/**
+ * Anthropic `/messages`
+ */
+"anthropic_messages" | 
+/**
+ * Ollama's native `/api/chat`
+ */
+"ollama_chat" | 
+/**
+ * Prompt-only `/completions`, for servers without chat templates
+ */
+"text_completion"
+/**
+ * How a spoken token's replacement is spaced against its neighbours
+ */
+export type PunctuationSpacing = 
+/**
+ * Attaches to the previous word, e.g. `,` or `)`
+ */
+"attach_left" | 
+/**
+ * Attaches to the next word, e.g. `(`
+ */
+"attach_right" | 
+/**
+ * Spaces on both sides, e.g. `&`
+ */
+"spaced" | 
+/**
+ * No spaces on either side, e.g. line breaks
+ */
+"joined"
 export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
-export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
+/**
+ * How a replacement rule treats letter case
+ */
+export type ReplacementCase = 
+/**
+ * Match the exact case
+ */
+"sensitive" | 
+/**
+ * Match any case and insert the replacement as written
+ */
+"insensitive" | 
+/**
+ * Match any case and give the replacement the case of the matched text
+ */
+"preserve"
+/**
+ * A find/replace pair applied to every transcription
+ */
+export type ReplacementRule = { find: string; replace: string; 
+/**
+ * Treat `find` as a regular expression; `replace` may then use `$1` or `${name}`
+ */
+is_regex?: boolean; case?: ReplacementCase; 
+/**
+ * Only match whole words
+ */
+whole_word?: boolean; enabled?: boolean }
+export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string; 
+/**
+ * Post-process with this prompt instead of the selected one
+ */
+prompt_id?: string | null; 
+/**
+ * Transcribe with this model instead of the selected one
+ */
+model_id?: string | null; language?: string | null }
+/**
+ * Text pasted in place of its spoken trigger, e.g. "insert my signature"
+ */
+export type Snippet = { id: string; trigger: string; content: string }
 export type SoundTheme = "marimba" | "pop" | "custom"
+/**
+ * A spoken phrase and the text it is written as
+ */
+export type SpokenToken = { phrase: string; replacement: string; spacing: PunctuationSpacing; 
+/**
+ * Only replace the phrase at a clause boundary, for words that are also
+ * ordinary vocabulary such as "period" or "punto"
+ */
+boundary_only?: boolean }
+/**
+ * A span of transcribed text, timed in seconds from the start of the recording.
+ */
+export type TimedText = { start: number; end: number; text: string }
+export type VoiceCommand = { id: string; phrase: string; action: VoiceCommandAction }
+/**
+ * What a voice command does when its phrase is spoken
+ */
+export type VoiceCommandAction = 
+/**
+ * Space-separated key combos, e.g. `ctrl+a` or `enter enter`
+ */
+{ type: "keys"; keys: string } | 
+/**
+ * Run through the platform shell without waiting for it to finish
+ */
+{ type: "shell"; command: string }
 
 /** tauri-specta globals **/
 
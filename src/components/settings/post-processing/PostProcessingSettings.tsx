@@ -186,6 +186,9 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
       const result = await commands.addPostProcessPrompt(
         draftName.trim(),
         draftText.trim(),
+        null,
+        null,
+        null,
       );
       if (result.status === "ok") {
         await refreshSettings();
@@ -205,6 +208,9 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
         selectedPromptId,
         draftName.trim(),
         draftText.trim(),
+        null,
+        null,
+        null,
       );
       await refreshSettings();
     } catch (error) {